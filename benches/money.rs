@@ -0,0 +1,185 @@
+//! Benchmarks for parsing, formatting, arithmetic and the SQL codec.
+//!
+//! Run with `cargo bench --features sql` to include the codec. The
+//! `display/*` groups compare `Display` against the String-building
+//! implementation it replaced, and the run starts by checking that `Display`
+//! does not allocate.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use postgres_money::{Money, MoneyFormat, NegativeStyle, Rate, Rounding};
+
+/// Counts allocations, to check that formatting makes none
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const AMOUNTS: [(&str, i64); 4] = [
+    ("cents", 5),
+    ("thousands", -123_456),
+    ("billions", 123_456_789_012),
+    ("min", i64::MIN),
+];
+
+const INPUTS: [(&str, &str); 6] = [
+    ("short", "5"),
+    ("cents", ".32"),
+    ("grouped", "$1,234.56"),
+    ("parentheses", "($123,456.78)"),
+    ("rounded", "$123.455"),
+    ("max", "92233720368547758.07"),
+];
+
+/// `Display` as it was before it wrote straight to the formatter
+fn legacy_display(money: Money) -> String {
+    let inner = money.inner();
+    let sign = if inner < 0 { "-" } else { "" };
+    let dollars = format!("{}", (inner / 100).abs());
+    let n = (inner % 100).abs();
+    let cents = format!("{}{}", if n < 10 { "0" } else { "" }, n);
+    format!("{}${}.{}", sign, dollars, cents)
+}
+
+fn assert_display_allocation_free() {
+    let mut out = String::with_capacity(64);
+    for (_, cents) in AMOUNTS {
+        out.clear();
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        write!(out, "{}", Money::from(cents)).unwrap();
+        write!(out, "{:>30.0}", Money::from(cents)).unwrap();
+        let after = ALLOCATIONS.load(Ordering::Relaxed);
+        assert_eq!(before, after, "Display allocated for {out}");
+    }
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, input) in INPUTS {
+        group.bench_with_input(BenchmarkId::new("parse_str", name), input, |b, input| {
+            b.iter(|| Money::parse_str(black_box(input)))
+        });
+    }
+    group.finish();
+}
+
+fn display(c: &mut Criterion) {
+    assert_display_allocation_free();
+
+    let mut group = c.benchmark_group("display");
+    let mut out = String::with_capacity(64);
+    for (name, cents) in AMOUNTS {
+        let money = Money::from(cents);
+        group.bench_with_input(BenchmarkId::new("display", name), &money, |b, money| {
+            b.iter(|| {
+                out.clear();
+                write!(out, "{}", black_box(money)).unwrap();
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("legacy", name), &money, |b, money| {
+            b.iter(|| {
+                out.clear();
+                out.push_str(&legacy_display(*black_box(money)));
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("format");
+    let formats = [
+        ("padded", None),
+        ("grouped", Some(MoneyFormat::new().grouping(true))),
+        (
+            "accounting",
+            Some(
+                MoneyFormat::new()
+                    .grouping(true)
+                    .negative_style(NegativeStyle::Parentheses),
+            ),
+        ),
+    ];
+    let money = Money::from(-123_456_789_012);
+    for (name, format) in &formats {
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                out.clear();
+                match format {
+                    Some(format) => write!(out, "{}", format.display(black_box(money))),
+                    None => write!(out, "{:>24}", black_box(money)),
+                }
+                .unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn arithmetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("arithmetic");
+    let (a, b) = (Money::from(123_456), Money::from(-7_890));
+    group.bench_function("add", |bench| bench.iter(|| black_box(a) + black_box(b)));
+    group.bench_function("checked_add", |bench| {
+        bench.iter(|| black_box(a).checked_add(black_box(b)))
+    });
+    group.bench_function("mul_i64", |bench| {
+        bench.iter(|| black_box(a) * black_box(3))
+    });
+    group.bench_function("div_f64", |bench| {
+        bench.iter(|| black_box(a) / black_box(3.0))
+    });
+    let rate = Rate::new(4125, 100_000).unwrap();
+    group.bench_function("mul_rate", |bench| {
+        bench.iter(|| black_box(a).mul_rate(black_box(rate), Rounding::HalfEven))
+    });
+    group.finish();
+}
+
+#[cfg(feature = "sql")]
+fn codec(c: &mut Criterion) {
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, ToSql, Type};
+
+    let mut group = c.benchmark_group("codec");
+    let mut buf = BytesMut::with_capacity(8);
+    for (name, cents) in AMOUNTS {
+        let money = Money::from(cents);
+        group.bench_with_input(BenchmarkId::new("to_sql", name), &money, |b, money| {
+            b.iter(|| {
+                buf.clear();
+                black_box(money).to_sql(&Type::MONEY, &mut buf).unwrap()
+            })
+        });
+
+        let mut encoded = BytesMut::new();
+        money.to_sql(&Type::MONEY, &mut encoded).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("from_sql", name),
+            &encoded,
+            |b, encoded| b.iter(|| Money::from_sql(&Type::MONEY, black_box(encoded)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "sql"))]
+fn codec(_: &mut Criterion) {}
+
+criterion_group!(benches, parse, display, arithmetic, codec);
+criterion_main!(benches);
@@ -0,0 +1,19 @@
+//! The binary `FromSql` decoder on arbitrary bytes: it must never panic, and
+//! anything it accepts must encode back to the same bytes.
+
+#![no_main]
+
+use bytes::BytesMut;
+use libfuzzer_sys::fuzz_target;
+use postgres_money::Money;
+use postgres_types::{FromSql, ToSql, Type};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(money) = Money::from_sql(&Type::MONEY, data) else {
+        return;
+    };
+
+    let mut buf = BytesMut::new();
+    money.to_sql(&Type::MONEY, &mut buf).unwrap();
+    assert_eq!(&buf[..], data);
+});
@@ -0,0 +1,22 @@
+//! `Money::parse_compact` on arbitrary text: it must never panic, and anything
+//! it accepts must read back the same when abbreviated with enough digits to
+//! be exact.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use postgres_money::compact::Suffixes;
+use postgres_money::{Money, Rounding};
+
+fuzz_target!(|input: &str| {
+    let Ok(money) = Money::parse_compact(input, Suffixes::ENGLISH) else {
+        return;
+    };
+
+    let formatted = money.format_compact(19, Rounding::HalfEven, Suffixes::ENGLISH);
+    assert_eq!(
+        Money::parse_compact(&formatted, Suffixes::ENGLISH),
+        Ok(money),
+        "{formatted}"
+    );
+});
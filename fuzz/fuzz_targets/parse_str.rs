@@ -0,0 +1,27 @@
+//! `Money::parse_str` on arbitrary text: it must never panic, and anything it
+//! accepts must read back the same from each output format.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use postgres_money::{Money, MoneyFormat, NegativeStyle};
+
+fuzz_target!(|input: &str| {
+    let Ok(money) = Money::parse_str(input) else {
+        return;
+    };
+
+    assert_eq!(Money::parse_str(&money.to_string()), Ok(money));
+    for style in [
+        NegativeStyle::Minus,
+        NegativeStyle::Parentheses,
+        NegativeStyle::TrailingMinus,
+        NegativeStyle::CrDr,
+    ] {
+        let formatted = MoneyFormat::new()
+            .grouping(true)
+            .negative_style(style)
+            .format(money);
+        assert_eq!(Money::parse_str(&formatted), Ok(money), "{formatted}");
+    }
+});
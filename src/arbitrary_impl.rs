@@ -0,0 +1,51 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::Money;
+
+/// Mostly uniform over every Money, with one value in eight taken from the
+/// boundaries such as [Money::min] and one cent either side of zero
+impl<'a> Arbitrary<'a> for Money {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Money> {
+        if u.ratio(1, 8)? {
+            return u.choose(&Money::BOUNDARIES).copied();
+        }
+        u.arbitrary().map(Money)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (_, upper) = <i64 as Arbitrary>::size_hint(depth);
+        (1, upper.map(|upper| upper + 2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reaches_boundaries() {
+        // A fixed pseudo-random stream, so the test is deterministic
+        let mut state: u64 = 1;
+        let bytes: Vec<u8> = (0..1 << 16)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 56) as u8
+            })
+            .collect();
+
+        let seen: Vec<Money> = bytes
+            .chunks(16)
+            .map(|chunk| Money::arbitrary(&mut Unstructured::new(chunk)).unwrap())
+            .collect();
+
+        assert!(seen.contains(&Money::min()));
+        assert!(seen.contains(&Money::max()));
+        assert!(seen.contains(&Money(-1)));
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let mut u = Unstructured::new(&[]);
+        assert!(Money::arbitrary(&mut u).is_ok());
+    }
+}
@@ -0,0 +1,220 @@
+//! Conversions between Money and Arrow arrays.
+//!
+//! Money can be stored as `Decimal128(19, 2)`, whose unscaled value is the
+//! cents, or as `Int64` cents. Both hold every Money exactly, so values
+//! survive a round trip through Parquet unchanged. Use [field] to describe a
+//! column so that readers can tell it holds money rather than a plain number.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::arrow::{self, Storage};
+//! use postgres_money::Money;
+//!
+//! let values = [Some(Money::from(123456)), None];
+//! let array = arrow::to_array(&values, Storage::Decimal128);
+//! let field = arrow::field("price", Storage::Decimal128, true);
+//!
+//! assert_eq!(field.data_type(), array.data_type());
+//! assert_eq!(arrow::from_array(&array).unwrap(), values);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow_array::{Array, ArrayRef, Decimal128Array, Int64Array};
+use arrow_schema::extension::{EXTENSION_TYPE_METADATA_KEY, EXTENSION_TYPE_NAME_KEY};
+use arrow_schema::{DataType, Field};
+
+use crate::{Error, Money};
+
+/// Extension type name recorded in the metadata of money fields
+pub const EXTENSION_NAME: &str = "postgres_money.money";
+
+/// Decimal precision wide enough for every Money
+pub const PRECISION: u8 = 19;
+
+/// Decimal scale of Money, in digits after the point
+pub const SCALE: i8 = 2;
+
+/// Physical Arrow type used for a money column
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Storage {
+    /// `Decimal128(19, 2)`, which most query engines show as dollars
+    #[default]
+    Decimal128,
+    /// `Int64` cents
+    Int64,
+}
+
+impl Storage {
+    /// The Arrow data type for this storage
+    pub fn data_type(self) -> DataType {
+        match self {
+            Storage::Decimal128 => DataType::Decimal128(PRECISION, SCALE),
+            Storage::Int64 => DataType::Int64,
+        }
+    }
+
+    fn unit(self) -> &'static str {
+        match self {
+            Storage::Decimal128 => r#"{"unit":"dollars"}"#,
+            Storage::Int64 => r#"{"unit":"cents"}"#,
+        }
+    }
+}
+
+/// A field for a money column, tagged with the [EXTENSION_NAME] extension
+/// type and the unit of its values
+pub fn field(name: impl Into<String>, storage: Storage, nullable: bool) -> Field {
+    Field::new(name, storage.data_type(), nullable).with_metadata(HashMap::from([
+        (
+            EXTENSION_TYPE_NAME_KEY.to_owned(),
+            EXTENSION_NAME.to_owned(),
+        ),
+        (
+            EXTENSION_TYPE_METADATA_KEY.to_owned(),
+            storage.unit().to_owned(),
+        ),
+    ]))
+}
+
+/// Whether `field` was created by [field]
+pub fn is_money_field(field: &Field) -> bool {
+    field.extension_type_name() == Some(EXTENSION_NAME)
+}
+
+/// Convert Money or `Option<Money>` values to a `Decimal128(19, 2)` array
+pub fn to_decimal128<T: Copy + Into<Option<Money>>>(values: &[T]) -> Decimal128Array {
+    values
+        .iter()
+        .map(|&value| value.into().map(|money| i128::from(money.inner())))
+        .collect::<Decimal128Array>()
+        .with_precision_and_scale(PRECISION, SCALE)
+        .expect("money precision and scale are valid")
+}
+
+/// Convert Money or `Option<Money>` values to an `Int64` array of cents
+pub fn to_int64<T: Copy + Into<Option<Money>>>(values: &[T]) -> Int64Array {
+    values
+        .iter()
+        .map(|&value| value.into().map(|money| money.inner()))
+        .collect()
+}
+
+/// Convert Money or `Option<Money>` values to an array of the given storage
+pub fn to_array<T: Copy + Into<Option<Money>>>(values: &[T], storage: Storage) -> ArrayRef {
+    match storage {
+        Storage::Decimal128 => Arc::new(to_decimal128(values)),
+        Storage::Int64 => Arc::new(to_int64(values)),
+    }
+}
+
+/// Convert a decimal array back to Money.
+///
+/// Returns `Error::InvalidArgument` if the scale is not 2, and
+/// `Error::OutOfRange` if a value does not fit in Money.
+pub fn from_decimal128(array: &Decimal128Array) -> Result<Vec<Option<Money>>, Error> {
+    if array.scale() != SCALE {
+        return Err(Error::InvalidArgument);
+    }
+
+    array
+        .iter()
+        .map(|value| value.map(Money::from_i128).transpose())
+        .collect()
+}
+
+/// Convert an `Int64` array of cents back to Money
+pub fn from_int64(array: &Int64Array) -> Vec<Option<Money>> {
+    array.iter().map(|value| value.map(Money)).collect()
+}
+
+/// Convert an array of either storage back to Money.
+///
+/// Returns `Error::InvalidArgument` for any other data type.
+pub fn from_array(array: &dyn Array) -> Result<Vec<Option<Money>>, Error> {
+    let any = array.as_any();
+    if let Some(array) = any.downcast_ref::<Decimal128Array>() {
+        from_decimal128(array)
+    } else if let Some(array) = any.downcast_ref::<Int64Array>() {
+        Ok(from_int64(array))
+    } else {
+        Err(Error::InvalidArgument)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::Int32Array;
+
+    use super::*;
+
+    const VALUES: [Money; 4] = [Money::min(), Money(-1), Money(123456), Money::max()];
+
+    #[test]
+    fn test_decimal128_round_trip() {
+        let array = to_decimal128(&VALUES);
+        assert_eq!(array.data_type(), &DataType::Decimal128(19, 2));
+        assert_eq!(array.null_count(), 0);
+        assert_eq!(array.value_as_string(2), "1234.56");
+
+        let back = from_decimal128(&array).unwrap();
+        assert_eq!(back, VALUES.map(Some));
+    }
+
+    #[test]
+    fn test_int64_round_trip() {
+        let array = to_int64(&VALUES);
+        assert_eq!(array.value(2), 123456);
+        assert_eq!(from_int64(&array), VALUES.map(Some));
+    }
+
+    #[test]
+    fn test_nulls() {
+        let values = [None, Some(Money(5)), None];
+        for storage in [Storage::Decimal128, Storage::Int64] {
+            let array = to_array(&values, storage);
+            assert_eq!(array.null_count(), 2);
+            assert_eq!(from_array(&array).unwrap(), values);
+        }
+    }
+
+    #[test]
+    fn test_from_decimal128_wrong_scale() {
+        let array = Decimal128Array::from(vec![100])
+            .with_precision_and_scale(19, 3)
+            .unwrap();
+        assert_eq!(from_decimal128(&array), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_from_decimal128_out_of_range() {
+        let array = Decimal128Array::from(vec![i128::from(i64::MAX) + 1])
+            .with_precision_and_scale(38, 2)
+            .unwrap();
+        assert_eq!(from_decimal128(&array), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn test_from_array_unsupported_type() {
+        let array = Int32Array::from(vec![1]);
+        assert_eq!(from_array(&array), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_field_metadata() {
+        let field = field("price", Storage::Int64, false);
+        assert_eq!(field.data_type(), &DataType::Int64);
+        assert!(!field.is_nullable());
+        assert!(is_money_field(&field));
+        assert_eq!(
+            field.metadata()[EXTENSION_TYPE_METADATA_KEY],
+            r#"{"unit":"cents"}"#
+        );
+        assert!(!is_money_field(&Field::new(
+            "price",
+            DataType::Int64,
+            false
+        )));
+    }
+}
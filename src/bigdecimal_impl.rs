@@ -0,0 +1,145 @@
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, RoundingMode};
+
+use crate::{Error, Money, Rounding};
+
+impl Money {
+    /// Convert from a `bigdecimal::BigDecimal`, rounding anything past the
+    /// cents according to `rounding`.
+    ///
+    /// Returns `Error::OutOfRange` if the result does not fit in Money.
+    ///
+    /// # Examples
+    /// ```
+    /// use bigdecimal::BigDecimal;
+    /// use postgres_money::{Money, Rounding};
+    ///
+    /// let decimal: BigDecimal = "-0.125".parse().unwrap();
+    /// let money = Money::from_big_decimal(&decimal, Rounding::Floor).unwrap();
+    ///
+    /// assert_eq!(money, Money::from(-13));
+    /// ```
+    pub fn from_big_decimal(decimal: &BigDecimal, rounding: Rounding) -> Result<Money, Error> {
+        let (digits, scale) = decimal.as_bigint_and_scale();
+
+        // Any nonzero value below this scale has at least 19 whole digits,
+        // and rescaling it to cents could allocate an enormous integer
+        if scale < -18 && *digits != BigInt::ZERO {
+            return Err(Error::OutOfRange);
+        }
+
+        let (cents, _) = decimal
+            .with_scale_round(2, rounding_mode(rounding))
+            .into_bigint_and_scale();
+        i64::try_from(cents)
+            .map(Money)
+            .map_err(|_| Error::OutOfRange)
+    }
+}
+
+fn rounding_mode(rounding: Rounding) -> RoundingMode {
+    match rounding {
+        Rounding::Ceiling => RoundingMode::Ceiling,
+        Rounding::Floor => RoundingMode::Floor,
+        Rounding::Down => RoundingMode::Down,
+        Rounding::Up => RoundingMode::Up,
+        Rounding::HalfUp => RoundingMode::HalfUp,
+        Rounding::HalfDown => RoundingMode::HalfDown,
+        Rounding::HalfEven => RoundingMode::HalfEven,
+    }
+}
+
+/// Rounds half away from zero, as Postgres does
+impl TryFrom<BigDecimal> for Money {
+    type Error = Error;
+
+    fn try_from(decimal: BigDecimal) -> Result<Money, Error> {
+        Money::from_big_decimal(&decimal, Rounding::HalfUp)
+    }
+}
+
+/// Exact, with a scale of 2
+impl From<Money> for BigDecimal {
+    fn from(money: Money) -> BigDecimal {
+        BigDecimal::new(BigInt::from(money.inner()), 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigDecimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for money in [
+            Money::min(),
+            Money(-1),
+            Money(0),
+            Money(123456),
+            Money::max(),
+        ] {
+            let decimal = BigDecimal::from(money);
+            assert_eq!(decimal.as_bigint_and_scale().1, 2);
+            assert_eq!(Money::try_from(decimal), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_from_big_decimal_scales() {
+        assert_eq!(Money::try_from(big("7")), Ok(Money(700)));
+        assert_eq!(Money::try_from(big("7.5")), Ok(Money(750)));
+        assert_eq!(Money::try_from(big("1.2e3")), Ok(Money(120000)));
+        assert_eq!(Money::try_from(big("-12.345")), Ok(Money(-1235)));
+        assert_eq!(
+            Money::from_big_decimal(&big("-12.345"), Rounding::HalfEven),
+            Ok(Money(-1234))
+        );
+        assert_eq!(
+            Money::from_big_decimal(&big("1e-1000"), Rounding::Ceiling),
+            Ok(Money(1))
+        );
+    }
+
+    #[test]
+    fn test_rounding_modes_agree() {
+        let ties = ["0.005", "0.015", "-0.005", "-0.015", "0.011", "-0.019"];
+        for rounding in [
+            Rounding::Ceiling,
+            Rounding::Floor,
+            Rounding::Down,
+            Rounding::Up,
+            Rounding::HalfUp,
+            Rounding::HalfDown,
+            Rounding::HalfEven,
+        ] {
+            for tie in ties {
+                let thousandths = big(tie).with_scale(3).into_bigint_and_scale().0;
+                let thousandths = i128::try_from(thousandths).unwrap();
+                assert_eq!(
+                    Money::from_big_decimal(&big(tie), rounding),
+                    Ok(Money(rounding.div(thousandths, 10) as i64)),
+                    "{tie} {rounding:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_big_decimal_out_of_range() {
+        assert_eq!(Money::try_from(big("1e19")), Err(Error::OutOfRange));
+        assert_eq!(Money::try_from(big("-1e1000000")), Err(Error::OutOfRange));
+        assert_eq!(
+            Money::try_from(big("92233720368547758.08")),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            Money::try_from(big("-92233720368547758.08")),
+            Ok(Money::min())
+        );
+        assert_eq!(Money::try_from(big("0e-1000000")), Ok(Money(0)));
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt;
+
+use crate::{Error, Money};
+
+/// Money that is known to lie between `MIN` and `MAX` cents inclusive.
+///
+/// The bounds are checked whenever a value is made, including when it is
+/// decoded from SQL or deserialized, so code holding one need not check
+/// again.
+///
+/// # Examples
+/// ```
+/// use postgres_money::{BoundedMoney, Error, Money, NonNegativeMoney};
+///
+/// // A refund capped at $500
+/// type Refund = BoundedMoney<0, 50_000>;
+///
+/// let refund = Refund::new(Money::from(12_500)).unwrap();
+/// assert_eq!(refund.get(), Money::from(12_500));
+/// assert_eq!(Refund::new(Money::from(50_001)), Err(Error::AboveMaximum));
+///
+/// let balance = NonNegativeMoney::new(Money::from(100)).unwrap();
+/// assert_eq!(balance.checked_sub(NonNegativeMoney::new(Money::from(101)).unwrap()), Err(Error::BelowMinimum));
+/// ```
+#[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Money", into = "Money")
+)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = crate::sql_types::Money),
+    diesel(sql_type = diesel::sql_types::Money)
+)]
+pub struct BoundedMoney<const MIN: i64, const MAX: i64>(Money);
+
+/// Money of zero or more
+pub type NonNegativeMoney = BoundedMoney<0, { i64::MAX }>;
+
+/// Money of one cent or more
+pub type PositiveMoney = BoundedMoney<1, { i64::MAX }>;
+
+impl<const MIN: i64, const MAX: i64> BoundedMoney<MIN, MAX> {
+    /// The smallest allowed value
+    pub const MIN: Money = Money(MIN);
+
+    /// The largest allowed value
+    pub const MAX: Money = Money(MAX);
+
+    const VALID_BOUNDS: () = assert!(MIN <= MAX, "BoundedMoney requires MIN <= MAX");
+
+    /// Check that `money` is within bounds, returning `Error::BelowMinimum`
+    /// or `Error::AboveMaximum` if not
+    pub const fn new(money: Money) -> Result<Self, Error> {
+        let () = Self::VALID_BOUNDS;
+        if money.0 < MIN {
+            Err(Error::BelowMinimum)
+        } else if money.0 > MAX {
+            Err(Error::AboveMaximum)
+        } else {
+            Ok(BoundedMoney(money))
+        }
+    }
+
+    /// The nearest value within bounds to `money`
+    pub const fn clamp(money: Money) -> Self {
+        let () = Self::VALID_BOUNDS;
+        if money.0 < MIN {
+            BoundedMoney(Money(MIN))
+        } else if money.0 > MAX {
+            BoundedMoney(Money(MAX))
+        } else {
+            BoundedMoney(money)
+        }
+    }
+
+    /// The amount as plain Money
+    pub const fn get(self) -> Money {
+        self.0
+    }
+
+    /// Add two amounts, returning `Error::AboveMaximum` or
+    /// `Error::BelowMinimum` if the sum is out of bounds
+    pub const fn checked_add(self, rhs: Self) -> Result<Self, Error> {
+        match (self.0).0.checked_add((rhs.0).0) {
+            Some(sum) => Self::new(Money(sum)),
+            None if (rhs.0).0 > 0 => Err(Error::AboveMaximum),
+            None => Err(Error::BelowMinimum),
+        }
+    }
+
+    /// Subtract two amounts, returning `Error::AboveMaximum` or
+    /// `Error::BelowMinimum` if the difference is out of bounds
+    pub const fn checked_sub(self, rhs: Self) -> Result<Self, Error> {
+        match (self.0).0.checked_sub((rhs.0).0) {
+            Some(difference) => Self::new(Money(difference)),
+            None if (rhs.0).0 < 0 => Err(Error::AboveMaximum),
+            None => Err(Error::BelowMinimum),
+        }
+    }
+
+    /// Add two amounts, stopping at the bounds
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self::clamp(Money((self.0).0.saturating_add((rhs.0).0)))
+    }
+
+    /// Subtract two amounts, stopping at the bounds
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self::clamp(Money((self.0).0.saturating_sub((rhs.0).0)))
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> TryFrom<Money> for BoundedMoney<MIN, MAX> {
+    type Error = Error;
+
+    fn try_from(money: Money) -> Result<Self, Error> {
+        Self::new(money)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> From<BoundedMoney<MIN, MAX>> for Money {
+    fn from(bounded: BoundedMoney<MIN, MAX>) -> Money {
+        bounded.0
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> fmt::Debug for BoundedMoney<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> fmt::Display for BoundedMoney<MIN, MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Cap = BoundedMoney<-100, 100>;
+
+    fn cap(cents: i64) -> Cap {
+        Cap::new(Money(cents)).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert_eq!(
+            NonNegativeMoney::new(Money(0)).map(|m| m.get()),
+            Ok(Money(0))
+        );
+        assert_eq!(NonNegativeMoney::new(Money(-1)), Err(Error::BelowMinimum));
+        assert_eq!(PositiveMoney::new(Money(0)), Err(Error::BelowMinimum));
+        assert_eq!(
+            PositiveMoney::new(Money::max()).map(Money::from),
+            Ok(Money::max())
+        );
+        assert_eq!(Cap::new(Money(101)), Err(Error::AboveMaximum));
+        assert_eq!(Cap::try_from(Money(-100)), Ok(cap(-100)));
+    }
+
+    #[test]
+    fn test_const_construction() {
+        const FEE: PositiveMoney = match PositiveMoney::new(Money(250)) {
+            Ok(fee) => fee,
+            Err(_) => panic!(),
+        };
+        assert_eq!(FEE.get(), Money(250));
+        assert_eq!(PositiveMoney::MIN, Money(1));
+        assert_eq!(Cap::MAX, Money(100));
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(Cap::clamp(Money(500)), cap(100));
+        assert_eq!(Cap::clamp(Money::min()), cap(-100));
+        assert_eq!(Cap::clamp(Money(7)), cap(7));
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(cap(60).checked_add(cap(40)), Ok(cap(100)));
+        assert_eq!(cap(60).checked_add(cap(41)), Err(Error::AboveMaximum));
+        assert_eq!(cap(-60).checked_sub(cap(41)), Err(Error::BelowMinimum));
+
+        let max = NonNegativeMoney::new(Money::max()).unwrap();
+        let one = NonNegativeMoney::new(Money(1)).unwrap();
+        assert_eq!(max.checked_add(one), Err(Error::AboveMaximum));
+        assert_eq!(one.checked_sub(max), Err(Error::BelowMinimum));
+        assert_eq!(max.checked_sub(max).map(|m| m.get()), Ok(Money(0)));
+    }
+
+    #[test]
+    fn test_wide_bounds_overflow() {
+        type Any = BoundedMoney<{ i64::MIN }, { i64::MAX }>;
+        let max = Any::new(Money::max()).unwrap();
+        let min = Any::new(Money::min()).unwrap();
+        assert_eq!(max.checked_add(max), Err(Error::AboveMaximum));
+        assert_eq!(min.checked_add(min), Err(Error::BelowMinimum));
+        assert_eq!(max.checked_sub(min), Err(Error::AboveMaximum));
+        assert_eq!(min.checked_sub(max), Err(Error::BelowMinimum));
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        assert_eq!(cap(90).saturating_add(cap(90)), cap(100));
+        assert_eq!(cap(-90).saturating_sub(cap(90)), cap(-100));
+
+        let max = PositiveMoney::new(Money::max()).unwrap();
+        assert_eq!(max.saturating_add(max), max);
+        assert_eq!(max.saturating_sub(max).get(), Money(1));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(cap(-5).to_string(), "-$0.05");
+        assert_eq!(format!("{:>8?}", cap(5)), "   $0.05");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates() {
+        let json = serde_json::to_string(&cap(-5)).unwrap();
+        assert_eq!(json, "-5");
+        assert_eq!(serde_json::from_str::<Cap>(&json).unwrap(), cap(-5));
+
+        let err = serde_json::from_str::<NonNegativeMoney>("-5").unwrap_err();
+        assert_eq!(err.to_string(), Error::BelowMinimum.to_string());
+    }
+}
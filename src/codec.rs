@@ -0,0 +1,80 @@
+//! The binary wire format shared by the database integrations.
+//!
+//! Postgres sends `money` as its underlying int8 in network byte order.
+
+use std::error::Error;
+
+use crate::{Money, MoneyFormat};
+
+pub(crate) type BoxError = Box<dyn Error + Sync + Send>;
+
+pub(crate) fn decode(buf: &[u8]) -> Result<Money, BoxError> {
+    let bytes: [u8; 8] = buf.try_into().map_err(|_| "invalid buffer size")?;
+    Ok(Money(i64::from_be_bytes(bytes)))
+}
+
+/// Decode the text format, which is locale-formatted output such as
+/// `-$1,234.56`
+#[cfg_attr(not(feature = "sqlx"), allow(dead_code))]
+pub(crate) fn decode_text(s: &str) -> Result<Money, BoxError> {
+    Ok(Money::parse_str(s)?)
+}
+
+pub(crate) fn encode(money: Money) -> [u8; 8] {
+    money.inner().to_be_bytes()
+}
+
+/// Encode as a plain decimal such as `-1234.56`, which Postgres accepts as
+/// `money` input without depending on the currency symbol of the locale
+#[cfg_attr(not(feature = "sea-orm"), allow(dead_code))]
+pub(crate) fn encode_text(money: Money) -> String {
+    MoneyFormat::new().show_symbol(false).format(money)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for money in [
+            Money::min(),
+            Money(-1),
+            Money(0),
+            Money(12345),
+            Money::max(),
+        ] {
+            assert_eq!(decode(&encode(money)).unwrap(), money);
+        }
+    }
+
+    #[test]
+    fn test_big_endian() {
+        assert_eq!(encode(Money(258)), [0, 0, 0, 0, 0, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(decode_text("-$1,234.56").unwrap(), Money(-123456));
+        assert_eq!(
+            decode_text("-$92,233,720,368,547,758.08").unwrap(),
+            Money::min()
+        );
+        assert!(decode_text("one dollar").is_err());
+    }
+
+    #[test]
+    fn test_encode_text() {
+        assert_eq!(encode_text(Money(-123456)), "-1234.56");
+        assert_eq!(encode_text(Money(5)), "0.05");
+        for money in [Money::min(), Money(-1), Money(0), Money::max()] {
+            assert_eq!(decode_text(&encode_text(money)).unwrap(), money);
+        }
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        assert!(decode(&[0; 7]).is_err());
+        assert!(decode(&[0; 9]).is_err());
+    }
+}
@@ -0,0 +1,434 @@
+//! Abbreviated amounts for dashboards, such as `$1.2K` or `$3.45M`.
+//!
+//! [Money::format_compact] shortens an amount to a number of significant
+//! digits and a suffix for its power of ten, and [Money::parse_compact]
+//! expands such an abbreviation back to an exact amount. The suffixes come
+//! from a [Suffixes] table, so locales that group by other powers of ten can
+//! supply their own.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::compact::Suffixes;
+//! use postgres_money::{Money, Rounding};
+//!
+//! let money = Money::from(345_678_900);
+//! assert_eq!(money.format_compact(3, Rounding::HalfUp, Suffixes::ENGLISH), "$3.46M");
+//! assert_eq!(money.format_compact(2, Rounding::Down, Suffixes::ENGLISH), "$3.4M");
+//!
+//! let money = Money::parse_compact("1.2k", Suffixes::ENGLISH).unwrap();
+//! assert_eq!(money, Money::from(120_000));
+//! ```
+
+use crate::{Error, Money, Rounding};
+
+/// Suffixes for powers of ten of dollars, such as `K` for thousands
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Suffixes<'a> {
+    units: &'a [(u32, &'a str)],
+}
+
+impl<'a> Suffixes<'a> {
+    /// `K`, `M`, `B`, `T` and `Q` for thousands up to quadrillions
+    pub const ENGLISH: Suffixes<'static> =
+        Suffixes::new(&[(3, "K"), (6, "M"), (9, "B"), (12, "T"), (15, "Q")]);
+
+    /// The largest exponent a suffix can have
+    pub const MAX_EXPONENT: u32 = 36;
+
+    /// A table of `(exponent, suffix)` pairs in increasing order of exponent,
+    /// such as `&[(5, " L"), (7, " Cr")]` for lakhs and crores. Use
+    /// [Suffixes::try_new] for tables that are not known at compile time.
+    ///
+    /// # Panics
+    /// Panics if the exponents are not strictly increasing or any is above
+    /// [Suffixes::MAX_EXPONENT].
+    pub const fn new(units: &'a [(u32, &'a str)]) -> Self {
+        match Suffixes::try_new(units) {
+            Ok(suffixes) => suffixes,
+            Err(_) => panic!("suffix exponents must increase and be at most MAX_EXPONENT"),
+        }
+    }
+
+    /// A table of `(exponent, suffix)` pairs as for [Suffixes::new],
+    /// returning `Error::InvalidArgument` if the exponents are not strictly
+    /// increasing or any is above [Suffixes::MAX_EXPONENT]
+    pub const fn try_new(units: &'a [(u32, &'a str)]) -> Result<Self, Error> {
+        let mut i = 0;
+        while i < units.len() {
+            if units[i].0 > Self::MAX_EXPONENT || (i > 0 && units[i - 1].0 >= units[i].0) {
+                return Err(Error::InvalidArgument);
+            }
+            i += 1;
+        }
+        Ok(Suffixes { units })
+    }
+
+    /// The exponent and suffix of the largest unit at or below `magnitude`
+    /// cents, and of the unit after it
+    fn unit_for(&self, magnitude: u128) -> ((u32, &'a str), Option<(u32, &'a str)>) {
+        let mut unit = (0, "");
+        let mut next = self.units.first().copied();
+        for (i, &(exponent, suffix)) in self.units.iter().enumerate() {
+            match pow10(exponent + 2) {
+                Some(scale) if magnitude >= scale => {
+                    unit = (exponent, suffix);
+                    next = self.units.get(i + 1).copied();
+                }
+                _ => break,
+            }
+        }
+        (unit, next)
+    }
+
+    fn exponent_of(&self, suffix: &str) -> Option<u32> {
+        if suffix.is_empty() {
+            return Some(0);
+        }
+        self.units
+            .iter()
+            .find(|(_, candidate)| candidate.trim().eq_ignore_ascii_case(suffix))
+            .map(|&(exponent, _)| exponent)
+    }
+}
+
+fn pow10(exponent: u32) -> Option<u128> {
+    10u128.checked_pow(exponent)
+}
+
+fn digits(n: u128) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// `cents` rounded to `decimals` places of the unit `10^exponent` dollars
+struct Scaled {
+    negative: bool,
+    units: u128,
+    decimals: u32,
+}
+
+impl Scaled {
+    fn new(cents: i128, exponent: u32, significant: u32, rounding: Rounding) -> Scaled {
+        let scale = exponent + 2;
+        let whole = cents.unsigned_abs() / pow10(scale).unwrap();
+        let whole_digits = digits(whole);
+
+        let mut decimals = if exponent == 0 {
+            // Below the first suffix, show whole dollars or whole cents
+            if whole_digits >= significant {
+                0
+            } else {
+                2
+            }
+        } else {
+            significant.saturating_sub(whole_digits).min(scale)
+        };
+
+        let mut units = rounding.div(cents, pow10(scale - decimals).unwrap() as i128);
+        // Rounding up to the next power of ten gains a whole digit
+        if exponent > 0 && decimals > 0 && digits(units.unsigned_abs()) > whole_digits + decimals {
+            decimals -= 1;
+            units = rounding.div(cents, pow10(scale - decimals).unwrap() as i128);
+        }
+
+        Scaled {
+            negative: units < 0,
+            units: units.unsigned_abs(),
+            decimals,
+        }
+    }
+
+    fn whole(&self) -> u128 {
+        self.units / pow10(self.decimals).unwrap()
+    }
+}
+
+impl Money {
+    /// Abbreviate the amount to `significant` digits, rounded according to
+    /// `rounding`, followed by the suffix for its unit. Digits before the
+    /// point are never dropped, so even one significant digit shows `$92Q`.
+    ///
+    /// Trailing zeros after the point are dropped, so `$12.0B` is shown as
+    /// `$12B`. Amounts below the first suffix are shown in whole dollars when
+    /// they have at least `significant` digits, and otherwise with cents.
+    pub fn format_compact(
+        self,
+        significant: u32,
+        rounding: Rounding,
+        suffixes: Suffixes,
+    ) -> String {
+        let significant = significant.max(1);
+        let cents = i128::from(self.inner());
+
+        let (mut unit, next) = suffixes.unit_for(cents.unsigned_abs());
+        let mut scaled = Scaled::new(cents, unit.0, significant, rounding);
+
+        // Rounding may carry into the next unit, as from 999.9K to 1M
+        if let Some(next) = next {
+            if pow10(next.0 - unit.0).is_some_and(|limit| scaled.whole() >= limit) {
+                unit = next;
+                scaled = Scaled::new(cents, unit.0, significant, rounding);
+            }
+        }
+
+        let sign = if scaled.negative { "-" } else { "" };
+        let mut out = format!("{sign}${}", scaled.whole());
+        if scaled.decimals > 0 {
+            let fraction = format!(
+                "{:0width$}",
+                scaled.units % pow10(scaled.decimals).unwrap(),
+                width = scaled.decimals as usize
+            );
+            let fraction = if unit.0 > 0 {
+                fraction.trim_end_matches('0')
+            } else {
+                &fraction
+            };
+            if !fraction.is_empty() {
+                out.push('.');
+                out.push_str(fraction);
+            }
+        }
+        out.push_str(unit.1);
+        out
+    }
+
+    /// Parse an amount abbreviated with a suffix from `suffixes`, such as
+    /// `$1.2K`, `-3m` or a plain `250`.
+    ///
+    /// Suffixes match regardless of case and of surrounding whitespace.
+    /// Digits beyond a cent are rounded half up, as [Money::parse_str] does.
+    /// Returns `Error::InvalidString` if the input is not of this form, and
+    /// `Error::OutOfRange` if the amount does not fit.
+    pub fn parse_compact(input: &str, suffixes: Suffixes) -> Result<Money, Error> {
+        let input = input.trim();
+        let (negative, rest) = match input.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, input),
+        };
+        let rest = rest.strip_prefix('$').unwrap_or(rest);
+
+        let number_len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (number, suffix) = rest.split_at(number_len);
+        let exponent = suffixes
+            .exponent_of(suffix.trim())
+            .ok_or(Error::InvalidString)?;
+
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        let fraction = fraction.trim_end_matches('0');
+        if !number.bytes().any(|b| b.is_ascii_digit()) || fraction.contains('.') {
+            return Err(Error::InvalidString);
+        }
+        // Rounding half up only looks at the digit after the cent
+        let fraction = &fraction[..fraction.len().min(exponent as usize + 3)];
+
+        let mut mantissa: i128 = 0;
+        for digit in whole.bytes().chain(fraction.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(i128::from(digit - b'0')))
+                .ok_or(Error::OutOfRange)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+
+        // The mantissa counts units of 10^-len(fraction), and a cent is 10^-2
+        let scale = i64::from(exponent) + 2 - fraction.len() as i64;
+        let cents = if scale >= 0 {
+            u32::try_from(scale)
+                .ok()
+                .and_then(pow10)
+                .and_then(|scale| mantissa.checked_mul(scale as i128))
+                .ok_or(Error::OutOfRange)?
+        } else {
+            match u32::try_from(-scale).ok().and_then(pow10) {
+                Some(scale) => Rounding::HalfUp.div(mantissa, scale as i128),
+                // Far below half a cent
+                None => 0,
+            }
+        };
+
+        Money::from_i128(cents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compact(cents: i64, significant: u32) -> String {
+        Money(cents).format_compact(significant, Rounding::HalfUp, Suffixes::ENGLISH)
+    }
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(compact(123_456, 2), "$1.2K");
+        assert_eq!(compact(345_000_000, 3), "$3.45M");
+        assert_eq!(compact(1_200_000_000_000, 3), "$12B");
+        assert_eq!(compact(-987_654_321, 2), "-$9.9M");
+        assert_eq!(compact(100_000, 4), "$1K");
+        assert_eq!(compact(123_456, 10), "$1.23456K");
+    }
+
+    #[test]
+    fn test_format_compact_small() {
+        assert_eq!(compact(0, 2), "$0.00");
+        assert_eq!(compact(1234, 2), "$12");
+        assert_eq!(compact(1234, 3), "$12.34");
+        assert_eq!(compact(99_999, 3), "$1K");
+        assert_eq!(compact(-40, 1), "$0");
+    }
+
+    #[test]
+    fn test_format_compact_carries() {
+        assert_eq!(compact(99_999_900, 2), "$1M");
+        assert_eq!(compact(99_950_000, 3), "$1M");
+        assert_eq!(compact(99_940_000, 3), "$999K");
+        assert_eq!(compact(999_500, 3), "$10K");
+        assert_eq!(compact(99_949, 3), "$999");
+        assert_eq!(compact(99_950, 4), "$999.50");
+    }
+
+    #[test]
+    fn test_format_compact_rounding() {
+        let money = Money(129_999);
+        assert_eq!(
+            money.format_compact(2, Rounding::Down, Suffixes::ENGLISH),
+            "$1.2K"
+        );
+        assert_eq!(
+            money.format_compact(2, Rounding::Up, Suffixes::ENGLISH),
+            "$1.3K"
+        );
+    }
+
+    #[test]
+    fn test_format_compact_extremes() {
+        assert_eq!(compact(i64::MAX, 3), "$92.2Q");
+        assert_eq!(compact(i64::MIN, 1), "-$92Q");
+        assert_eq!(compact(i64::MIN, 30), "-$92.23372036854775808Q");
+    }
+
+    #[test]
+    fn test_custom_suffixes() {
+        let indian = Suffixes::new(&[(5, " L"), (7, " Cr")]);
+        let money = Money(1_234_567_800);
+        assert_eq!(
+            money.format_compact(3, Rounding::HalfUp, indian),
+            "$1.23 Cr"
+        );
+        assert_eq!(
+            Money::parse_compact("$1.23 cr", indian),
+            Ok(Money(1_230_000_000))
+        );
+        assert_eq!(Money::parse_compact("4.5L", indian), Ok(Money(45_000_000)));
+    }
+
+    #[test]
+    fn test_suffixes_try_new() {
+        for units in [
+            &[(6, "M"), (3, "K")][..],
+            &[(3, "K"), (3, "k")],
+            &[(3, "K"), (Suffixes::MAX_EXPONENT + 1, "X")],
+            &[(3, "K"), (u32::MAX, "X")],
+        ] {
+            assert_eq!(Suffixes::try_new(units), Err(Error::InvalidArgument));
+        }
+        assert_eq!(
+            Suffixes::try_new(&[(5, " L"), (7, " Cr")]),
+            Ok(Suffixes::new(&[(5, " L"), (7, " Cr")]))
+        );
+        assert!(Suffixes::try_new(&[]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "suffix exponents must increase")]
+    fn test_suffixes_new_panics() {
+        Suffixes::new(&[(6, "M"), (3, "K")]);
+    }
+
+    #[test]
+    fn test_suffixes_largest_exponent() {
+        let suffixes = Suffixes::new(&[(Suffixes::MAX_EXPONENT, "U")]);
+        assert_eq!(
+            Money::max().format_compact(3, Rounding::HalfUp, suffixes),
+            "$92233720368547758"
+        );
+        assert_eq!(Money::parse_compact("1U", suffixes), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn test_parse_compact() {
+        let parse = |s| Money::parse_compact(s, Suffixes::ENGLISH);
+        assert_eq!(parse("1.2k"), Ok(Money(120_000)));
+        assert_eq!(parse("$3M"), Ok(Money(300_000_000)));
+        assert_eq!(parse("-$3.45 M"), Ok(Money(-345_000_000)));
+        assert_eq!(parse("250"), Ok(Money(25_000)));
+        assert_eq!(parse(".5"), Ok(Money(50)));
+        assert_eq!(parse("0"), Ok(Money(0)));
+        assert_eq!(parse("1.2345678K"), Ok(Money(123_457)));
+        assert_eq!(
+            parse("1.00000000000000000000000000000000000000000K"),
+            Ok(Money(100_000))
+        );
+        assert_eq!(parse("92.23372036854775807Q"), Ok(Money::max()));
+    }
+
+    #[test]
+    fn test_parse_compact_long_fraction() {
+        let long = "1.000000000000000000000000000000000000000001";
+        assert_eq!(
+            Money::parse_compact(long, Suffixes::ENGLISH),
+            Money::parse_str(long)
+        );
+        assert_eq!(
+            Money::parse_compact(
+                "1.0050000000000000000000000000000000000009k",
+                Suffixes::ENGLISH
+            ),
+            Ok(Money(100_500))
+        );
+        assert_eq!(
+            Money::parse_compact(
+                "-0.004999999999999999999999999999999999999",
+                Suffixes::ENGLISH
+            ),
+            Ok(Money(0))
+        );
+        assert_eq!(
+            Money::parse_compact(
+                "2.34599999999999999999999999999999999999999M",
+                Suffixes::ENGLISH
+            ),
+            Ok(Money(234_600_000))
+        );
+    }
+
+    #[test]
+    fn test_parse_compact_invalid() {
+        let parse = |s| Money::parse_compact(s, Suffixes::ENGLISH);
+        for input in ["", "$", "K", "1.2.3K", "1X", "--1", "1,000"] {
+            assert_eq!(parse(input), Err(Error::InvalidString), "{input}");
+        }
+        assert_eq!(parse("92.3Q"), Err(Error::OutOfRange));
+        assert_eq!(parse("-92.3Q"), Err(Error::OutOfRange));
+        assert_eq!(
+            parse("99999999999999999999999999999999999999999"),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_round_trip_when_exact() {
+        for cents in [120_000, -345_000_000, 1_200_000_000_000, 5, -1234] {
+            let formatted = compact(cents, 19);
+            assert_eq!(
+                Money::parse_compact(&formatted, Suffixes::ENGLISH),
+                Ok(Money(cents)),
+                "{formatted}"
+            );
+        }
+    }
+}
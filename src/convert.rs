@@ -0,0 +1,294 @@
+use crate::{Error, Money, Rounding};
+
+impl Money {
+    /// Construct Money from a whole number of cents
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::Money;
+    /// let money = Money::from_cents(324023040222);
+    ///
+    /// assert_eq!("$3240230402.22", money.to_string());
+    /// ```
+    pub const fn from_cents(cents: i64) -> Money {
+        Money(cents)
+    }
+
+    /// Construct Money from a whole number of dollars, returning
+    /// `Error::OutOfRange` if it does not fit
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::{Error, Money};
+    ///
+    /// assert_eq!(Money::from_dollars(-12), Ok(Money::from_cents(-1200)));
+    /// assert_eq!(Money::from_dollars(i64::MAX), Err(Error::OutOfRange));
+    /// ```
+    pub const fn from_dollars(dollars: i64) -> Result<Money, Error> {
+        match dollars.checked_mul(100) {
+            Some(cents) => Ok(Money(cents)),
+            None => Err(Error::OutOfRange),
+        }
+    }
+
+    /// Construct Money from dollars and cents.
+    ///
+    /// Both parts carry the sign of the amount, so `-$0.50` is `(0, -50)` and
+    /// `-$1.50` is `(-1, -50)`. Returns `Error::InvalidArgument` if `cents` is
+    /// not between -99 and 99 or has the opposite sign to `dollars`, and
+    /// `Error::OutOfRange` if the amount does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::Money;
+    ///
+    /// let money = Money::from_dollars_cents(-1, -50).unwrap();
+    /// assert_eq!("-$1.50", money.to_string());
+    /// ```
+    pub const fn from_dollars_cents(dollars: i64, cents: i64) -> Result<Money, Error> {
+        if cents <= -100 || cents >= 100 || (dollars > 0 && cents < 0) || (dollars < 0 && cents > 0)
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        match dollars.checked_mul(100) {
+            Some(whole) => match whole.checked_add(cents) {
+                Some(inner) => Ok(Money(inner)),
+                None => Err(Error::OutOfRange),
+            },
+            None => Err(Error::OutOfRange),
+        }
+    }
+
+    /// Construct Money from a number of dollars, rounding to a whole cent
+    /// according to `rounding`.
+    ///
+    /// The exact binary value of `dollars` is rounded, so no error creeps in
+    /// from scaling it first: `1.005` is slightly less than one dollar and half
+    /// a cent, and rounds to `$1.00` even with [Rounding::HalfUp]. Returns
+    /// `Error::InvalidArgument` for NaN and `Error::OutOfRange` if the result
+    /// does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::{Money, Rounding};
+    ///
+    /// let money = Money::from_f64_rounded(19.999, Rounding::Down).unwrap();
+    /// assert_eq!("$19.99", money.to_string());
+    /// ```
+    pub fn from_f64_rounded(dollars: f64, rounding: Rounding) -> Result<Money, Error> {
+        if dollars.is_nan() {
+            return Err(Error::InvalidArgument);
+        }
+        if dollars.is_infinite() {
+            return Err(Error::OutOfRange);
+        }
+
+        let (mantissa, exponent) = decode_f64(dollars);
+        let cents = mantissa * 100;
+
+        if exponent >= 0 {
+            // At least 2^52 dollars once the exponent is this large
+            if exponent > 64 || cents.abs() > i128::MAX >> exponent {
+                return Err(Error::OutOfRange);
+            }
+            return Money::from_i128(cents << exponent);
+        }
+
+        // Beyond this shift every nonzero value is well under half a cent,
+        // which rounds the same way as any other such value
+        match -exponent {
+            shift @ ..=100 => Money::from_i128(rounding.div(cents, 1 << shift)),
+            _ => Money::from_i128(rounding.div(cents.signum(), 1 << 100)),
+        }
+    }
+}
+
+/// Split a finite float into `mantissa * 2^exponent`
+fn decode_f64(x: f64) -> (i128, i32) {
+    let bits = x.to_bits();
+    let sign = if bits >> 63 == 0 { 1 } else { -1 };
+    let biased = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = (bits & ((1 << 52) - 1)) as i128;
+
+    if biased == 0 {
+        (sign * fraction, -1074)
+    } else {
+        (sign * (fraction | 1 << 52), biased - 1075)
+    }
+}
+
+/// Interprets the value as cents
+impl From<i64> for Money {
+    fn from(cents: i64) -> Money {
+        Money(cents)
+    }
+}
+
+/// The number of cents
+impl From<Money> for i64 {
+    fn from(money: Money) -> i64 {
+        money.inner()
+    }
+}
+
+/// Interprets the value as cents
+impl TryFrom<i128> for Money {
+    type Error = Error;
+
+    fn try_from(cents: i128) -> Result<Money, Error> {
+        Money::from_i128(cents)
+    }
+}
+
+/// Interprets the value as cents
+impl TryFrom<u64> for Money {
+    type Error = Error;
+
+    fn try_from(cents: u64) -> Result<Money, Error> {
+        i64::try_from(cents)
+            .map(Money)
+            .map_err(|_| Error::OutOfRange)
+    }
+}
+
+/// Interprets the value as dollars, rounding half away from zero to a whole
+/// cent as [Money::from_f64_rounded] does
+impl TryFrom<f64> for Money {
+    type Error = Error;
+
+    fn try_from(dollars: f64) -> Result<Money, Error> {
+        Money::from_f64_rounded(dollars, Rounding::HalfUp)
+    }
+}
+
+/// Parses the string as [Money::parse_str] does
+impl TryFrom<&str> for Money {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Money, Error> {
+        Money::parse_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_i64_round_trip() {
+        for cents in [i64::MIN, -1, 0, 123456, i64::MAX] {
+            assert_eq!(i64::from(Money::from(cents)), cents);
+            assert_eq!(Money::from(cents), Money::from_cents(cents));
+        }
+    }
+
+    #[test]
+    fn test_from_dollars() {
+        assert_eq!(Money::from_dollars(5), Ok(Money(500)));
+        assert_eq!(
+            Money::from_dollars(i64::MAX / 100),
+            Ok(Money(i64::MAX / 100 * 100))
+        );
+        assert_eq!(
+            Money::from_dollars(i64::MIN / 100 - 1),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_from_dollars_cents() {
+        assert_eq!(Money::from_dollars_cents(12, 34), Ok(Money(1234)));
+        assert_eq!(Money::from_dollars_cents(0, -5), Ok(Money(-5)));
+        assert_eq!(Money::from_dollars_cents(-3, 0), Ok(Money(-300)));
+        assert_eq!(
+            Money::from_dollars_cents(92233720368547758, 7),
+            Ok(Money::max())
+        );
+        assert_eq!(
+            Money::from_dollars_cents(-92233720368547758, -8),
+            Ok(Money::min())
+        );
+        assert_eq!(
+            Money::from_dollars_cents(92233720368547758, 8),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_from_dollars_cents_invalid() {
+        for (dollars, cents) in [(0, 100), (0, -100), (1, -1), (-1, 1)] {
+            assert_eq!(
+                Money::from_dollars_cents(dollars, cents),
+                Err(Error::InvalidArgument)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_f64_rounded() {
+        assert_eq!(Money::try_from(19.99), Ok(Money(1999)));
+        assert_eq!(Money::try_from(-0.125), Ok(Money(-13)));
+        assert_eq!(
+            Money::from_f64_rounded(-0.125, Rounding::HalfEven),
+            Ok(Money(-12))
+        );
+        assert_eq!(
+            Money::from_f64_rounded(-0.125, Rounding::Ceiling),
+            Ok(Money(-12))
+        );
+        // 1.005 is really 1.00499999999999989...
+        assert_eq!(Money::try_from(1.005), Ok(Money(100)));
+        assert_eq!(Money::try_from(-0.0), Ok(Money(0)));
+        assert_eq!(Money::try_from(1e15), Ok(Money(100_000_000_000_000_000)));
+    }
+
+    #[test]
+    fn test_from_f64_tiny() {
+        let tiny = f64::from_bits(1);
+        assert_eq!(Money::try_from(tiny), Ok(Money(0)));
+        assert_eq!(Money::from_f64_rounded(tiny, Rounding::Up), Ok(Money(1)));
+        assert_eq!(
+            Money::from_f64_rounded(-tiny, Rounding::Floor),
+            Ok(Money(-1))
+        );
+        assert_eq!(
+            Money::from_f64_rounded(-1e-300, Rounding::HalfUp),
+            Ok(Money(0))
+        );
+    }
+
+    #[test]
+    fn test_from_f64_out_of_range() {
+        assert_eq!(Money::try_from(1e17), Err(Error::OutOfRange));
+        assert_eq!(Money::try_from(f64::MAX), Err(Error::OutOfRange));
+        assert_eq!(Money::try_from(f64::NEG_INFINITY), Err(Error::OutOfRange));
+        assert_eq!(Money::try_from(f64::NAN), Err(Error::InvalidArgument));
+        // The closest double to the minimum is just below it
+        assert_eq!(
+            Money::try_from(-92233720368547758.08),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            Money::try_from(-9.2e16),
+            Ok(Money(-9_200_000_000_000_000_000))
+        );
+    }
+
+    #[test]
+    fn test_try_from_wide_integers() {
+        assert_eq!(Money::try_from(i128::from(i64::MIN)), Ok(Money::min()));
+        assert_eq!(
+            Money::try_from(i128::from(i64::MAX) + 1),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(Money::try_from(i64::MAX as u64), Ok(Money::max()));
+        assert_eq!(Money::try_from(u64::MAX), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(Money::try_from("$1,234.56"), Ok(Money(123456)));
+        assert_eq!(Money::try_from("abc"), Err(Error::InvalidString));
+    }
+}
@@ -0,0 +1,516 @@
+//! Reading and writing Money in CSV files.
+//!
+//! The serde helpers in [cents], [decimal] and [postgres] are meant for
+//! `#[serde(with = "...")]` on fields of records read or written with the
+//! `csv` crate, and each has an `option` submodule that maps empty cells to
+//! `None`. [Column] instead streams a single column out of a
+//! [Reader], reporting the line and column of any cell that
+//! fails to parse.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::csv::{Column, Format};
+//! use postgres_money::Money;
+//!
+//! let data = "sku,price\nA1,\"$1,234.56\"\nB2,\n";
+//! let mut reader = csv::Reader::from_reader(data.as_bytes());
+//!
+//! let prices = Column::by_name(&mut reader, "price", Format::Postgres)
+//!     .unwrap()
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//!
+//! assert_eq!(prices, [Some(Money::from(123456)), None]);
+//! assert_eq!(Format::Decimal.format(Money::from(-123456)), "-1234.56");
+//! ```
+
+use std::fmt;
+use std::io;
+use std::num::IntErrorKind;
+
+use ::csv::{Reader, StringRecord};
+
+use crate::{Error, Money, MoneyFormat};
+
+/// How Money is written to, and read from, a CSV cell
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// A whole number of cents, such as `-123456`
+    Cents,
+    /// A plain decimal number of dollars, such as `-1234.56`
+    Decimal,
+    /// Postgres output in the `en_US` locale, such as `-$1,234.56`
+    #[default]
+    Postgres,
+}
+
+impl Format {
+    /// Format `money` as a cell
+    pub fn format(self, money: Money) -> String {
+        match self {
+            Format::Cents => money.inner().to_string(),
+            Format::Decimal => MoneyFormat::new().show_symbol(false).format(money),
+            Format::Postgres => MoneyFormat::new().grouping(true).format(money),
+        }
+    }
+
+    /// Parse a cell, ignoring surrounding whitespace.
+    ///
+    /// [Format::Decimal] and [Format::Postgres] both accept anything
+    /// [Money::parse_str] does.
+    pub fn parse(self, cell: &str) -> Result<Money, Error> {
+        let cell = cell.trim();
+        match self {
+            Format::Cents => cell.parse().map(Money).map_err(|e| match e.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Error::OutOfRange,
+                _ => Error::InvalidString,
+            }),
+            Format::Decimal | Format::Postgres => Money::parse_str(cell),
+        }
+    }
+}
+
+/// A cell that could not be parsed as Money
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FieldError {
+    /// Line of the file the record starts on, counting from 1
+    pub line: u64,
+    /// Column of the cell, counting from 1
+    pub column: usize,
+    /// Header of the column, if the reader has headers
+    pub header: Option<String>,
+    /// Contents of the cell
+    pub value: String,
+    /// Why the contents are not Money
+    pub error: Error,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if let Some(header) = &self.header {
+            write!(f, " ({header})")?;
+        }
+        write!(f, ": {}: {:?}", self.error, self.value)
+    }
+}
+
+impl std::error::Error for FieldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// An error reading a money column
+#[derive(Debug)]
+pub enum ReadError {
+    /// The underlying reader failed or the CSV was malformed
+    Csv(::csv::Error),
+    /// No header matched the requested column name
+    MissingColumn(String),
+    /// A cell could not be parsed
+    Field(FieldError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Csv(e) => e.fmt(f),
+            ReadError::MissingColumn(name) => write!(f, "no column named {name:?}"),
+            ReadError::Field(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Csv(e) => Some(e),
+            ReadError::MissingColumn(_) => None,
+            ReadError::Field(e) => Some(e),
+        }
+    }
+}
+
+impl From<::csv::Error> for ReadError {
+    fn from(e: ::csv::Error) -> ReadError {
+        ReadError::Csv(e)
+    }
+}
+
+/// Iterator over the Money in one column of a CSV reader.
+///
+/// Records are read one at a time into a single reused buffer. Empty cells,
+/// and records too short to have the column, yield `None`.
+pub struct Column<'r, R> {
+    reader: &'r mut Reader<R>,
+    record: StringRecord,
+    index: usize,
+    header: Option<String>,
+    format: Format,
+}
+
+impl<'r, R: io::Read> Column<'r, R> {
+    /// Read the column with the given header
+    pub fn by_name(
+        reader: &'r mut Reader<R>,
+        name: &str,
+        format: Format,
+    ) -> Result<Column<'r, R>, ReadError> {
+        let index = reader
+            .headers()?
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| ReadError::MissingColumn(name.to_owned()))?;
+        Ok(Column::new(reader, index, Some(name.to_owned()), format))
+    }
+
+    /// Read the column at `index`, counting from 0
+    pub fn by_index(
+        reader: &'r mut Reader<R>,
+        index: usize,
+        format: Format,
+    ) -> Result<Column<'r, R>, ReadError> {
+        let header = if reader.has_headers() {
+            reader.headers()?.get(index).map(str::to_owned)
+        } else {
+            None
+        };
+        Ok(Column::new(reader, index, header, format))
+    }
+
+    fn new(
+        reader: &'r mut Reader<R>,
+        index: usize,
+        header: Option<String>,
+        format: Format,
+    ) -> Column<'r, R> {
+        Column {
+            reader,
+            record: StringRecord::new(),
+            index,
+            header,
+            format,
+        }
+    }
+
+    fn parse_current(&self) -> Result<Option<Money>, ReadError> {
+        let cell = match self.record.get(self.index) {
+            Some(cell) if !cell.trim().is_empty() => cell,
+            _ => return Ok(None),
+        };
+
+        self.format.parse(cell).map(Some).map_err(|error| {
+            ReadError::Field(FieldError {
+                line: self.record.position().map_or(0, |p| p.line()),
+                column: self.index + 1,
+                header: self.header.clone(),
+                value: cell.to_owned(),
+                error,
+            })
+        })
+    }
+}
+
+impl<R: io::Read> Iterator for Column<'_, R> {
+    type Item = Result<Option<Money>, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reader.read_record(&mut self.record) {
+            Ok(true) => Some(self.parse_current()),
+            Ok(false) => None,
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+mod visitor {
+    use std::fmt;
+
+    use serde::de::{self, Deserializer, Visitor};
+
+    use super::Format;
+    use crate::Money;
+
+    pub(super) struct MoneyVisitor(pub(super) Format);
+
+    impl Visitor<'_> for MoneyVisitor {
+        type Value = Money;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "money formatted as {:?}", self.0)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Money, E> {
+            self.0.parse(v).map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Money, E> {
+            self.visit_str(&v.to_string())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Money, E> {
+            self.visit_str(&v.to_string())
+        }
+    }
+
+    pub(super) struct OptionVisitor(pub(super) Format);
+
+    impl<'de> Visitor<'de> for OptionVisitor {
+        type Value = Option<Money>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "an empty cell or money formatted as {:?}", self.0)
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Option<Money>, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Option<Money>, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<Option<Money>, D::Error> {
+            d.deserialize_str(self)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Option<Money>, E> {
+            if v.trim().is_empty() {
+                Ok(None)
+            } else {
+                MoneyVisitor(self.0).visit_str(v).map(Some)
+            }
+        }
+    }
+}
+
+macro_rules! serde_format_module {
+    ($($(#[$doc:meta])* $name:ident => $format:expr;)+) => ($(
+        $(#[$doc])*
+        pub mod $name {
+            use serde::{Deserializer, Serializer};
+
+            use super::visitor::MoneyVisitor;
+            use super::Format;
+            use crate::Money;
+
+            /// Serialize Money in this format
+            pub fn serialize<S: Serializer>(money: &Money, s: S) -> Result<S::Ok, S::Error> {
+                s.serialize_str(&$format.format(*money))
+            }
+
+            /// Deserialize Money in this format
+            pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Money, D::Error> {
+                d.deserialize_str(MoneyVisitor($format))
+            }
+
+            /// The same format for `Option<Money>`, where `None` is an empty cell
+            pub mod option {
+                use serde::{Deserializer, Serializer};
+
+                use super::super::visitor::OptionVisitor;
+                use super::super::Format;
+                use crate::Money;
+
+                /// Serialize `Option<Money>` in this format
+                pub fn serialize<S: Serializer>(
+                    money: &Option<Money>,
+                    s: S,
+                ) -> Result<S::Ok, S::Error> {
+                    match money {
+                        Some(money) => super::serialize(money, s),
+                        None => s.serialize_none(),
+                    }
+                }
+
+                /// Deserialize `Option<Money>` in this format
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    d: D,
+                ) -> Result<Option<Money>, D::Error> {
+                    d.deserialize_option(OptionVisitor($format))
+                }
+            }
+        }
+    )+)
+}
+
+serde_format_module! {
+    /// Serde helpers for [Format::Cents]
+    cents => Format::Cents;
+    /// Serde helpers for [Format::Decimal]
+    decimal => Format::Decimal;
+    /// Serde helpers for [Format::Postgres]
+    postgres => Format::Postgres;
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Row {
+        sku: String,
+        #[serde(with = "postgres")]
+        price: Money,
+        #[serde(with = "cents::option")]
+        discount: Option<Money>,
+    }
+
+    fn reader(data: &str) -> Reader<&[u8]> {
+        Reader::from_reader(data.as_bytes())
+    }
+
+    #[test]
+    fn test_format() {
+        let money = Money(-123456789);
+        assert_eq!(Format::Cents.format(money), "-123456789");
+        assert_eq!(Format::Decimal.format(money), "-1234567.89");
+        assert_eq!(Format::Postgres.format(money), "-$1,234,567.89");
+        assert_eq!(Format::Postgres.format(Money(5)), "$0.05");
+        assert_eq!(Format::Postgres.format(Money(100000)), "$1,000.00");
+        assert_eq!(
+            Format::Postgres.format(Money::min()),
+            "-$92,233,720,368,547,758.08"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for format in [Format::Cents, Format::Decimal, Format::Postgres] {
+            for money in [
+                Money::min(),
+                Money(-1),
+                Money(0),
+                Money(123456),
+                Money::max(),
+            ] {
+                assert_eq!(format.parse(&format.format(money)), Ok(money));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_cents() {
+        assert_eq!(Format::Cents.parse(" 1999 "), Ok(Money(1999)));
+        assert_eq!(Format::Cents.parse("19.99"), Err(Error::InvalidString));
+        assert_eq!(
+            Format::Cents.parse("9223372036854775808"),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_serde_helpers() {
+        let data = "sku,price,discount\nA1,\"$1,234.56\",250\nB2,$0.99,\n";
+        let rows: Vec<Row> = reader(data)
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(
+            rows,
+            [
+                Row {
+                    sku: "A1".to_owned(),
+                    price: Money(123456),
+                    discount: Some(Money(250)),
+                },
+                Row {
+                    sku: "B2".to_owned(),
+                    price: Money(99),
+                    discount: None,
+                },
+            ]
+        );
+
+        let mut writer = ::csv::Writer::from_writer(vec![]);
+        for row in &rows {
+            writer.serialize(row).unwrap();
+        }
+        let written = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(written, data);
+    }
+
+    #[test]
+    fn test_serde_helper_error() {
+        let data = "sku,price,discount\nA1,$1.2.3,\n";
+        let err = reader(data)
+            .deserialize::<Row>()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid input syntax for type money"));
+    }
+
+    #[test]
+    fn test_column_by_name() {
+        let data = "sku,price\nA1,$1.00\nB2,\nC3\n";
+        let mut reader = ::csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(data.as_bytes());
+
+        let prices: Vec<_> = Column::by_name(&mut reader, "price", Format::Decimal)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(prices, [Some(Money(100)), None, None]);
+    }
+
+    #[test]
+    fn test_column_by_index_without_headers() {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader("1,2\n3,4\n".as_bytes());
+
+        let cents: Vec<_> = Column::by_index(&mut reader, 1, Format::Cents)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(cents, [Some(Money(2)), Some(Money(4))]);
+    }
+
+    #[test]
+    fn test_column_missing() {
+        let mut reader = reader("sku,price\n");
+        assert!(matches!(
+            Column::by_name(&mut reader, "cost", Format::Postgres),
+            Err(ReadError::MissingColumn(name)) if name == "cost"
+        ));
+    }
+
+    #[test]
+    fn test_column_error_location() {
+        let data = "sku,price\nA1,$1.00\nB2,\"$1,00.0.1\"\nC3,$3.00\n";
+        let mut reader = reader(data);
+        let mut column = Column::by_name(&mut reader, "price", Format::Postgres).unwrap();
+
+        assert_eq!(column.next().unwrap().unwrap(), Some(Money(100)));
+        let err = match column.next().unwrap() {
+            Err(ReadError::Field(err)) => err,
+            other => panic!("unexpected {other:?}"),
+        };
+        assert_eq!(
+            err,
+            FieldError {
+                line: 3,
+                column: 2,
+                header: Some("price".to_owned()),
+                value: "$1,00.0.1".to_owned(),
+                error: Error::InvalidString,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            r#"line 3, column 2 (price): invalid input syntax for type money: "$1,00.0.1""#
+        );
+
+        // Reading carries on after a bad cell
+        assert_eq!(column.next().unwrap().unwrap(), Some(Money(300)));
+        assert!(column.next().is_none());
+    }
+}
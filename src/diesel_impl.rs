@@ -0,0 +1,261 @@
+use std::io::Write;
+
+use diesel::deserialize::{self, FromSql};
+use diesel::expression::{AsExpression, Expression, TypedExpressionType};
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{Nullable, SingleValue, SqlType};
+
+use crate::{codec, BoundedMoney, Money};
+
+/// Diesel SQL types for use in `table!` declarations
+pub mod sql_types {
+    use diesel::query_builder::QueryId;
+    use diesel::sql_types::{ops, SqlType};
+
+    /// The Postgres `money` SQL type.
+    ///
+    /// Unlike `diesel::sql_types::Money`, this type supports addition and
+    /// subtraction in queries. Postgres defines `sum(money)` but no
+    /// `avg(money)`, so rather than implement Diesel's `Foldable`, which
+    /// requires both, sum columns with [money_sum](crate::money_sum).
+    ///
+    /// The `table!` macro only generates `+` and `-` operators for columns of
+    /// Diesel's built-in numeric and date types, so use
+    /// [MoneyExpressionMethods](crate::MoneyExpressionMethods) on columns.
+    ///
+    /// # Examples
+    /// ```
+    /// use diesel::prelude::*;
+    /// use postgres_money::{money_sum, Money, MoneyExpressionMethods};
+    ///
+    /// diesel::table! {
+    ///     use diesel::sql_types::Integer;
+    ///     use postgres_money::sql_types::Money;
+    ///
+    ///     orders (id) {
+    ///         id -> Integer,
+    ///         total -> Money,
+    ///         refunded -> Money,
+    ///     }
+    /// }
+    ///
+    /// let query = orders::table
+    ///     .filter(orders::total.minus(orders::refunded).gt(Money::from(1000)))
+    ///     .select(money_sum(orders::total));
+    ///
+    /// let sql = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+    /// assert!(sql.starts_with(r#"SELECT sum("orders"."total") FROM "orders""#));
+    /// ```
+    #[derive(Clone, Copy, Debug, Default, QueryId, SqlType)]
+    #[diesel(postgres_type(oid = 790, array_oid = 791))]
+    pub struct Money;
+
+    impl ops::Add for Money {
+        type Rhs = Money;
+        type Output = Money;
+    }
+
+    impl ops::Sub for Money {
+        type Rhs = Money;
+        type Output = Money;
+    }
+}
+
+macro_rules! impl_diesel_sql_for_money {
+    ($($sql_type:ty)+) => ($(
+        impl FromSql<$sql_type, Pg> for Money {
+            fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                codec::decode(bytes.as_bytes())
+            }
+        }
+
+        impl ToSql<$sql_type, Pg> for Money {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                out.write_all(&codec::encode(*self))?;
+                Ok(IsNull::No)
+            }
+        }
+
+        /// Decoding fails if the value is out of bounds
+        impl<const MIN: i64, const MAX: i64> FromSql<$sql_type, Pg> for BoundedMoney<MIN, MAX> {
+            fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                decode_bounded(bytes.as_bytes())
+            }
+        }
+
+        impl<const MIN: i64, const MAX: i64> ToSql<$sql_type, Pg> for BoundedMoney<MIN, MAX> {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                out.write_all(&codec::encode(self.get()))?;
+                Ok(IsNull::No)
+            }
+        }
+    )+)
+}
+
+fn decode_bounded<const MIN: i64, const MAX: i64>(
+    bytes: &[u8],
+) -> deserialize::Result<BoundedMoney<MIN, MAX>> {
+    Ok(BoundedMoney::new(codec::decode(bytes)?)?)
+}
+
+// Diesel's own type is supported too, so that schemas generated by
+// `diesel print-schema` keep working
+impl_diesel_sql_for_money! { sql_types::Money diesel::sql_types::Money }
+
+diesel::infix_operator!(MoneyAdd, " + ", sql_types::Money, backend: Pg);
+diesel::infix_operator!(MoneySub, " - ", sql_types::Money, backend: Pg);
+
+/// The money SQL type or its nullable form
+pub trait MoneySqlType: SqlType + TypedExpressionType {}
+
+impl MoneySqlType for sql_types::Money {}
+impl MoneySqlType for Nullable<sql_types::Money> {}
+
+diesel::define_sql_function! {
+    /// Represents a SQL `sum` over an expression of the money SQL type,
+    /// which is NULL when there are no rows
+    #[aggregate]
+    #[sql_name = "sum"]
+    fn money_sum<ST: MoneySqlType + SingleValue>(expr: ST) -> Nullable<sql_types::Money>;
+}
+
+/// Arithmetic on Diesel expressions of the money SQL type
+pub trait MoneyExpressionMethods: Expression + Sized
+where
+    Self::SqlType: MoneySqlType,
+{
+    /// Creates a SQL `+` expression
+    fn plus<T>(self, rhs: T) -> MoneyAdd<Self, T::Expression>
+    where
+        T: AsExpression<Self::SqlType>,
+    {
+        MoneyAdd::new(self, rhs.as_expression())
+    }
+
+    /// Creates a SQL `-` expression
+    fn minus<T>(self, rhs: T) -> MoneySub<Self, T::Expression>
+    where
+        T: AsExpression<Self::SqlType>,
+    {
+        MoneySub::new(self, rhs.as_expression())
+    }
+}
+
+impl<E> MoneyExpressionMethods for E
+where
+    E: Expression,
+    E::SqlType: MoneySqlType,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::pg::Pg;
+    use diesel::prelude::*;
+
+    use super::{decode_bounded, money_sum, MoneyExpressionMethods};
+    use crate::Money;
+
+    diesel::table! {
+        use diesel::sql_types::{Integer, Nullable};
+        use crate::sql_types::Money;
+
+        accounts (id) {
+            id -> Integer,
+            balance -> Money,
+            credit -> Nullable<Money>,
+        }
+    }
+
+    diesel::table! {
+        legacy (id) {
+            id -> Integer,
+            balance -> Money,
+        }
+    }
+
+    fn assert_sql_type<T, E: Expression<SqlType = T>>(_: E) {}
+
+    #[test]
+    fn test_arithmetic_sql() {
+        let query = accounts::table.select((
+            accounts::balance.plus(Money(100)),
+            accounts::balance.minus(accounts::balance),
+            accounts::credit.plus(accounts::credit),
+        ));
+
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.contains(r#""accounts"."balance" + $1"#));
+        assert!(sql.contains(r#""accounts"."balance" - "accounts"."balance""#));
+        assert!(sql.contains(r#""accounts"."credit" + "accounts"."credit""#));
+    }
+
+    #[test]
+    fn test_arithmetic_types() {
+        assert_sql_type::<crate::sql_types::Money, _>(accounts::balance.plus(Money(1)));
+        assert_sql_type::<diesel::sql_types::Nullable<crate::sql_types::Money>, _>(
+            accounts::credit.minus(Money(1)),
+        );
+    }
+
+    #[test]
+    fn test_comparisons_and_sum() {
+        let query = accounts::table
+            .filter(accounts::balance.ge(Money(0)))
+            .filter(accounts::credit.lt(Money(-100)))
+            .select(money_sum(accounts::balance));
+
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.starts_with(r#"SELECT sum("accounts"."balance") FROM "accounts""#));
+        assert!(sql.contains(r#""accounts"."balance" >= $1"#));
+        assert!(sql.contains(r#""accounts"."credit" < $2"#));
+    }
+
+    #[test]
+    fn test_sum_returns_optional_money() {
+        assert_sql_type::<diesel::sql_types::Nullable<crate::sql_types::Money>, _>(money_sum(
+            accounts::balance,
+        ));
+    }
+
+    #[test]
+    fn test_generic_expressions_add() {
+        // Expressions not built by the table macro pick up `+` from the SQL type
+        let query = accounts::table.select(money_sum(accounts::balance) + Some(Money(1)));
+
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.contains(r#"(sum("accounts"."balance") + $1)"#));
+    }
+
+    #[test]
+    fn test_diesel_money_type_binds() {
+        let query = legacy::table.filter(legacy::balance.eq(Money(5)));
+
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.contains(r#""legacy"."balance" = $1"#));
+        assert!(sql.ends_with("-- binds: [$0.05]"));
+    }
+
+    #[test]
+    fn test_bounded_binds() {
+        let limit = crate::PositiveMoney::new(Money(500)).unwrap();
+        let query = accounts::table.filter(accounts::balance.ge(limit));
+
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.ends_with("-- binds: [$5.00]"));
+    }
+
+    #[test]
+    fn test_bounded_decode_validates() {
+        let money: crate::PositiveMoney = decode_bounded(&500i64.to_be_bytes()).unwrap();
+        assert_eq!(money.get(), Money(500));
+
+        let err = decode_bounded::<0, { i64::MAX }>(&(-1i64).to_be_bytes()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<crate::Error>(),
+            Some(&crate::Error::BelowMinimum)
+        );
+        assert!(decode_bounded::<0, 100>(&[0; 4]).is_err());
+    }
+}
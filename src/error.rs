@@ -1,6 +1,44 @@
+use std::fmt;
+
+/// Errors returned by fallible Money operations
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
+    /// A value did not fit in the range of Money
     OutOfRange,
+    /// Input contained digits that could not be parsed
     ParseInt,
+    /// Input was not valid money syntax
     InvalidString,
+    /// An argument was outside the domain accepted by the operation
+    InvalidArgument,
+    /// An amount was in a different currency than expected
+    CurrencyMismatch,
+    /// An amount was below the minimum of a bounded money type
+    BelowMinimum,
+    /// An amount was above the maximum of a bounded money type
+    AboveMaximum,
+    /// The debits of a transaction did not equal its credits
+    Unbalanced,
+    /// An account path was used with a different kind than its hierarchy
+    AccountKindMismatch,
 }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Error::OutOfRange => "value is out of range for type money",
+            Error::ParseInt => "invalid digits in money input",
+            Error::InvalidString => "invalid input syntax for type money",
+            Error::InvalidArgument => "invalid argument",
+            Error::CurrencyMismatch => "currency does not match",
+            Error::BelowMinimum => "value is below the minimum allowed",
+            Error::AboveMaximum => "value is above the maximum allowed",
+            Error::Unbalanced => "debits do not equal credits",
+            Error::AccountKindMismatch => "account kind does not match its hierarchy",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,620 @@
+//! Interest accrual and loan amortization on [`Money`].
+//!
+//! Interest is computed with exact integer math: each accrual multiplies the
+//! balance by a [`Rate`] and a day-count fraction, then rounds once to whole
+//! cents with the caller's [`Rounding`] mode.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::finance::{self, Date, DayCount};
+//! use postgres_money::{Money, Rate, Rounding};
+//!
+//! let start = Date::new(2024, 1, 1).unwrap();
+//! let end = Date::new(2024, 7, 1).unwrap();
+//! let interest = finance::simple_interest(
+//!     Money::from(1_000_000),
+//!     Rate::percent(5),
+//!     start,
+//!     end,
+//!     DayCount::Thirty360,
+//!     Rounding::HalfUp,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!("$250.00", interest.to_string());
+//! ```
+
+use crate::{Error, Money, Rate, Rounding};
+
+/// A proleptic Gregorian calendar date, used to measure accrual periods
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Construct a date, returning `Error::InvalidArgument` if it does not exist
+    pub fn new(year: i32, month: u8, day: u8) -> Result<Date, Error> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(Error::InvalidArgument);
+        }
+        Ok(Date { year, month, day })
+    }
+
+    /// Calendar year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Month of the year, from 1 to 12
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Day of the month, starting at 1
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Days since 1970-01-01
+    fn ordinal(&self) -> i64 {
+        // Howard Hinnant's days_from_civil
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Convention used to turn a pair of dates into a fraction of a year
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DayCount {
+    /// Actual days elapsed over a 360 day year
+    Act360,
+    /// Actual days elapsed over a 365 day year
+    Act365,
+    /// Every month has 30 days and the year has 360 (ISDA bond basis)
+    Thirty360,
+}
+
+impl DayCount {
+    /// The year fraction between `start` and `end` as `(days, days_per_year)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::finance::{Date, DayCount};
+    ///
+    /// let start = Date::new(2024, 1, 31).unwrap();
+    /// let end = Date::new(2024, 3, 1).unwrap();
+    ///
+    /// assert_eq!(DayCount::Act360.year_fraction(start, end), (30, 360));
+    /// assert_eq!(DayCount::Thirty360.year_fraction(start, end), (31, 360));
+    /// ```
+    pub fn year_fraction(self, start: Date, end: Date) -> (i64, i64) {
+        match self {
+            DayCount::Act360 => (end.ordinal() - start.ordinal(), 360),
+            DayCount::Act365 => (end.ordinal() - start.ordinal(), 365),
+            DayCount::Thirty360 => {
+                let d1 = start.day.min(30);
+                let d2 = if d1 == 30 { end.day.min(30) } else { end.day };
+                let days = 360 * (i64::from(end.year) - i64::from(start.year))
+                    + 30 * (i64::from(end.month) - i64::from(start.month))
+                    + (i64::from(d2) - i64::from(d1));
+                (days, 360)
+            }
+        }
+    }
+}
+
+/// Interest on `balance` at an annual `rate` over `days / days_per_year` of a
+/// year, rounded once
+fn accrue(
+    balance: Money,
+    rate: Rate,
+    (days, days_per_year): (i64, i64),
+    rounding: Rounding,
+) -> Result<Money, Error> {
+    let num = i128::from(balance.inner())
+        .checked_mul(i128::from(rate.numerator()))
+        .and_then(|n| n.checked_mul(i128::from(days)))
+        .ok_or(Error::OutOfRange)?;
+    let den = i128::from(rate.denominator()) * i128::from(days_per_year);
+    Money::from_i128(rounding.div(num, den))
+}
+
+/// Simple interest on `principal` at an annual `rate` from `start` to `end`.
+///
+/// Returns `Error::InvalidArgument` if `end` is before `start`, and
+/// `Error::OutOfRange` if the interest does not fit in Money.
+pub fn simple_interest(
+    principal: Money,
+    rate: Rate,
+    start: Date,
+    end: Date,
+    day_count: DayCount,
+    rounding: Rounding,
+) -> Result<Money, Error> {
+    if end < start {
+        return Err(Error::InvalidArgument);
+    }
+    accrue(
+        principal,
+        rate,
+        day_count.year_fraction(start, end),
+        rounding,
+    )
+}
+
+/// Compound interest on `principal` at an annual `rate`, compounding on each
+/// of `dates` after the first.
+///
+/// Interest for each period is rounded to whole cents before being added to
+/// the balance, as a bank statement would show it. The returned value is the
+/// total interest; the final balance is `principal` plus this amount.
+///
+/// Returns `Error::InvalidArgument` if `dates` is not in ascending order, and
+/// `Error::OutOfRange` if the balance overflows.
+///
+/// # Examples
+/// ```
+/// use postgres_money::finance::{self, Date, DayCount};
+/// use postgres_money::{Money, Rate, Rounding};
+///
+/// // Quarterly compounding over one year
+/// let dates: Vec<Date> = [(2024, 1), (2024, 4), (2024, 7), (2024, 10), (2025, 1)]
+///     .iter()
+///     .map(|&(y, m)| Date::new(y, m, 1).unwrap())
+///     .collect();
+/// let interest = finance::compound_interest(
+///     Money::from(1_000_000),
+///     Rate::percent(4),
+///     &dates,
+///     DayCount::Thirty360,
+///     Rounding::HalfUp,
+/// )
+/// .unwrap();
+///
+/// assert_eq!("$406.04", interest.to_string());
+/// ```
+pub fn compound_interest(
+    principal: Money,
+    rate: Rate,
+    dates: &[Date],
+    day_count: DayCount,
+    rounding: Rounding,
+) -> Result<Money, Error> {
+    if dates.windows(2).any(|w| w[1] < w[0]) {
+        return Err(Error::InvalidArgument);
+    }
+
+    let balance = dates.windows(2).try_fold(principal, |balance, w| {
+        let interest = accrue(balance, rate, day_count.year_fraction(w[0], w[1]), rounding)?;
+        balance.checked_add(interest).ok_or(Error::OutOfRange)
+    })?;
+
+    balance.checked_sub(principal).ok_or(Error::OutOfRange)
+}
+
+/// One row of an amortization [`Schedule`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Installment {
+    /// One-based period number
+    pub period: u32,
+    /// Total amount paid this period
+    pub payment: Money,
+    /// Part of the payment that reduces the balance
+    pub principal: Money,
+    /// Part of the payment that covers interest
+    pub interest: Money,
+    /// Balance remaining after the payment
+    pub balance: Money,
+}
+
+/// The longest term [`Schedule::amortize`] accepts, in years
+pub const MAX_TERM_YEARS: u32 = 100;
+
+/// The most payments a year [`Schedule::amortize`] accepts, one a day
+pub const MAX_PERIODS_PER_YEAR: u32 = 366;
+
+/// A level-payment loan amortization schedule
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Schedule {
+    installments: Vec<Installment>,
+}
+
+impl Schedule {
+    /// Amortize `principal` over `periods` equal payments at an annual `rate`
+    /// with `periods_per_year` payments each year.
+    ///
+    /// Interest for each period is `balance * rate / periods_per_year`, rounded
+    /// with `rounding`. The level payment is the smallest whole-cent amount that
+    /// retires the loan within the term, and the final payment is reduced by
+    /// any remainder so that the principal column sums to `principal` exactly.
+    /// The schedule ends early if the balance reaches zero before the term does.
+    ///
+    /// Returns `Error::InvalidArgument` if `principal` or `rate` is negative,
+    /// either period count is zero, `periods_per_year` is over
+    /// [`MAX_PERIODS_PER_YEAR`], or `periods` is over `periods_per_year *`
+    /// [`MAX_TERM_YEARS`].
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::finance::Schedule;
+    /// use postgres_money::{Money, Rate, Rounding};
+    ///
+    /// let schedule = Schedule::amortize(Money::from(100_000), Rate::percent(12), 12, 12, Rounding::HalfUp).unwrap();
+    /// let first = schedule.installments()[0];
+    ///
+    /// assert_eq!("$88.85", first.payment.to_string());
+    /// assert_eq!("$10.00", first.interest.to_string());
+    /// assert_eq!(Money::from(100_000), schedule.total_principal().unwrap());
+    /// assert_eq!(Money::none(), schedule.installments()[11].balance);
+    /// ```
+    pub fn amortize(
+        principal: Money,
+        rate: Rate,
+        periods_per_year: u32,
+        periods: u32,
+        rounding: Rounding,
+    ) -> Result<Schedule, Error> {
+        if principal < Money::none()
+            || rate.is_negative()
+            || !(1..=MAX_PERIODS_PER_YEAR).contains(&periods_per_year)
+            || !(1..=periods_per_year * MAX_TERM_YEARS).contains(&periods)
+        {
+            return Err(Error::InvalidArgument);
+        }
+
+        let period_rate = rate.checked_mul(Rate::new(1, i64::from(periods_per_year))?)?;
+        let interest_on = |balance: Money| balance.mul_rate(period_rate, rounding);
+
+        // Whether paying `payment` every period retires the loan in time. A
+        // balance that grows until it overflows is clearly never retired.
+        let retires = |payment: Money| {
+            let mut balance = principal;
+            for _ in 0..periods {
+                if balance <= Money::none() {
+                    return true;
+                }
+                let owed = interest_on(balance)
+                    .ok()
+                    .and_then(|interest| balance.checked_add(interest));
+                match owed {
+                    Some(owed) => balance = owed - payment,
+                    None => return false,
+                }
+            }
+            balance <= Money::none()
+        };
+
+        // Binary search the smallest payment that retires the loan. Paying off
+        // the principal plus the first period's interest at once always does.
+        let upper = principal
+            .checked_add(interest_on(principal)?)
+            .ok_or(Error::OutOfRange)?;
+        let (mut low, mut high) = (0, upper.inner());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if retires(Money(mid)) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        let payment = Money(low);
+
+        let mut installments = Vec::new();
+        let mut balance = principal;
+        for period in 1..=periods {
+            if balance <= Money::none() {
+                break;
+            }
+            let interest = interest_on(balance)?;
+            let owed = balance.checked_add(interest).ok_or(Error::OutOfRange)?;
+            let paid = if period == periods || owed < payment {
+                owed
+            } else {
+                payment
+            };
+            let principal_paid = paid - interest;
+            balance = balance - principal_paid;
+
+            installments.push(Installment {
+                period,
+                payment: paid,
+                principal: principal_paid,
+                interest,
+                balance,
+            });
+        }
+
+        Ok(Schedule { installments })
+    }
+
+    /// Every installment in payment order
+    pub fn installments(&self) -> &[Installment] {
+        &self.installments
+    }
+
+    /// Sum of all payments
+    pub fn total_payment(&self) -> Result<Money, Error> {
+        crate::stats::sum(self.installments.iter().map(|i| i.payment))
+    }
+
+    /// Sum of all interest paid
+    pub fn total_interest(&self) -> Result<Money, Error> {
+        crate::stats::sum(self.installments.iter().map(|i| i.interest))
+    }
+
+    /// Sum of all principal repaid
+    pub fn total_principal(&self) -> Result<Money, Error> {
+        crate::stats::sum(self.installments.iter().map(|i| i.principal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        assert_eq!(Date::new(2023, 2, 29), Err(Error::InvalidArgument));
+        assert_eq!(Date::new(2024, 13, 1), Err(Error::InvalidArgument));
+        assert_eq!(Date::new(2024, 4, 31), Err(Error::InvalidArgument));
+        assert_eq!(Date::new(2024, 1, 0), Err(Error::InvalidArgument));
+        assert!(Date::new(2024, 2, 29).is_ok());
+        assert!(Date::new(2000, 2, 29).is_ok());
+        assert_eq!(Date::new(1900, 2, 29), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_ordinal() {
+        assert_eq!(date(1970, 1, 1).ordinal(), 0);
+        assert_eq!(date(2000, 3, 1).ordinal(), 11_017);
+        assert_eq!(date(1969, 12, 31).ordinal(), -1);
+    }
+
+    #[test]
+    fn test_actual_day_counts() {
+        let (start, end) = (date(2024, 1, 1), date(2025, 1, 1));
+        assert_eq!(DayCount::Act360.year_fraction(start, end), (366, 360));
+        assert_eq!(DayCount::Act365.year_fraction(start, end), (366, 365));
+    }
+
+    #[test]
+    fn test_thirty_360_end_of_month() {
+        let fraction = |s, e| DayCount::Thirty360.year_fraction(s, e);
+
+        assert_eq!(fraction(date(2024, 1, 31), date(2024, 2, 29)), (29, 360));
+        assert_eq!(fraction(date(2024, 1, 30), date(2024, 3, 31)), (60, 360));
+        assert_eq!(fraction(date(2024, 1, 15), date(2024, 3, 31)), (76, 360));
+        assert_eq!(fraction(date(2024, 1, 1), date(2025, 1, 1)), (360, 360));
+    }
+
+    #[test]
+    fn test_simple_interest() {
+        let interest = |day_count| {
+            simple_interest(
+                Money(1_000_000),
+                Rate::percent(10),
+                date(2024, 1, 1),
+                date(2024, 2, 1),
+                day_count,
+                Rounding::HalfUp,
+            )
+        };
+
+        // 31 days of 10% on $10,000
+        assert_eq!(interest(DayCount::Act360), Ok(Money(8611)));
+        assert_eq!(interest(DayCount::Act365), Ok(Money(8493)));
+        assert_eq!(interest(DayCount::Thirty360), Ok(Money(8333)));
+    }
+
+    #[test]
+    fn test_simple_interest_backwards() {
+        assert_eq!(
+            simple_interest(
+                Money(100),
+                Rate::percent(10),
+                date(2024, 2, 1),
+                date(2024, 1, 1),
+                DayCount::Act360,
+                Rounding::HalfUp,
+            ),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_simple_interest_overflow() {
+        assert_eq!(
+            simple_interest(
+                Money::max(),
+                Rate::percent(100),
+                date(2024, 1, 1),
+                date(2026, 1, 1),
+                DayCount::Thirty360,
+                Rounding::HalfUp,
+            ),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_compound_interest_rounds_each_period() {
+        let dates = [date(2024, 1, 1), date(2024, 1, 2), date(2024, 1, 3)];
+        // Each day accrues 100 * 10% / 360 = 0.0277 cents, which rounds away
+        let interest = compound_interest(
+            Money(100),
+            Rate::percent(10),
+            &dates,
+            DayCount::Act360,
+            Rounding::HalfUp,
+        );
+        assert_eq!(interest, Ok(Money(0)));
+
+        let interest = compound_interest(
+            Money(100),
+            Rate::percent(10),
+            &dates,
+            DayCount::Act360,
+            Rounding::Ceiling,
+        );
+        assert_eq!(interest, Ok(Money(2)));
+    }
+
+    #[test]
+    fn test_compound_interest_unsorted() {
+        let dates = [date(2024, 1, 2), date(2024, 1, 1)];
+        assert_eq!(
+            compound_interest(
+                Money(100),
+                Rate::percent(10),
+                &dates,
+                DayCount::Act360,
+                Rounding::HalfUp
+            ),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_compound_interest_no_periods() {
+        assert_eq!(
+            compound_interest(
+                Money(100),
+                Rate::percent(10),
+                &[date(2024, 1, 1)],
+                DayCount::Act360,
+                Rounding::HalfUp
+            ),
+            Ok(Money(0))
+        );
+    }
+
+    #[test]
+    fn test_amortize_reconciles() {
+        let principal = Money(25_000_000);
+        let schedule = Schedule::amortize(
+            principal,
+            Rate::new(65, 1000).unwrap(),
+            12,
+            360,
+            Rounding::HalfUp,
+        )
+        .unwrap();
+        let installments = schedule.installments();
+
+        assert_eq!(installments.len(), 360);
+        // The textbook payment is $1580.17, which would leave a few cents
+        // owing after 360 periods of rounded interest
+        assert_eq!(installments[0].payment, Money(158_018));
+        assert_eq!(installments[359].balance, Money(0));
+        assert!(installments[359].payment <= installments[0].payment);
+        assert_eq!(schedule.total_principal(), Ok(principal));
+        assert_eq!(
+            schedule.total_payment(),
+            Ok(principal + schedule.total_interest().unwrap())
+        );
+
+        for row in installments {
+            assert_eq!(row.payment, row.principal + row.interest);
+        }
+    }
+
+    #[test]
+    fn test_amortize_zero_rate() {
+        let schedule =
+            Schedule::amortize(Money(1000), Rate::zero(), 12, 3, Rounding::HalfUp).unwrap();
+        let payments: Vec<Money> = schedule.installments().iter().map(|i| i.payment).collect();
+
+        assert_eq!(payments, vec![Money(334), Money(334), Money(332)]);
+        assert_eq!(schedule.total_interest(), Ok(Money(0)));
+    }
+
+    #[test]
+    fn test_amortize_zero_principal() {
+        let schedule =
+            Schedule::amortize(Money(0), Rate::percent(5), 12, 2, Rounding::HalfUp).unwrap();
+
+        assert!(schedule.installments().is_empty());
+        assert_eq!(schedule.total_payment(), Ok(Money(0)));
+    }
+
+    #[test]
+    fn test_amortize_invalid() {
+        let amortize = |principal, rate, ppy, periods| {
+            Schedule::amortize(principal, rate, ppy, periods, Rounding::HalfUp)
+        };
+
+        assert_eq!(
+            amortize(Money(-1), Rate::percent(5), 12, 12),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            amortize(Money(1), Rate::percent(-5), 12, 12),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            amortize(Money(1), Rate::percent(5), 0, 12),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            amortize(Money(1), Rate::percent(5), 12, 0),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_amortize_term_limit() {
+        let amortize = |ppy, periods| {
+            Schedule::amortize(Money(1), Rate::zero(), ppy, periods, Rounding::HalfUp)
+        };
+
+        let longest = amortize(MAX_PERIODS_PER_YEAR, MAX_PERIODS_PER_YEAR * MAX_TERM_YEARS);
+        assert!(longest.is_ok());
+        assert_eq!(amortize(1, MAX_TERM_YEARS + 1), Err(Error::InvalidArgument));
+        assert_eq!(amortize(12, u32::MAX), Err(Error::InvalidArgument));
+        assert_eq!(
+            amortize(MAX_PERIODS_PER_YEAR + 1, 1),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            amortize(3_579_139, 4_294_966_800),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(amortize(u32::MAX, 1), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_amortize_stops_when_repaid() {
+        // One cent a month at 0% retires a cent in a single payment
+        let schedule =
+            Schedule::amortize(Money(1), Rate::zero(), 12, 360, Rounding::HalfUp).unwrap();
+        assert_eq!(schedule.installments().len(), 1);
+        assert_eq!(schedule.installments()[0].balance, Money(0));
+    }
+}
@@ -0,0 +1,430 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::{Money, Rounding};
+
+/// How negative amounts are marked
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum NegativeStyle {
+    /// A leading minus sign: `-$1.00`
+    #[default]
+    Minus,
+    /// Accounting parentheses: `($1.00)`
+    Parentheses,
+    /// A trailing minus sign: `$1.00-`
+    TrailingMinus,
+    /// A `CR` suffix on negative (credit) amounts and a `DR` suffix on
+    /// positive (debit) ones: `$1.00 CR`, `$1.00 DR`
+    CrDr,
+}
+
+/// A configurable way of displaying Money.
+///
+/// Every format that keeps the `$` symbol and at least two decimals is read
+/// back exactly by [Money::parse_str].
+///
+/// The default matches `Display`: a leading minus, a `$` symbol, two
+/// decimals and no digit grouping. Width, fill, alignment, `+`, `0` and
+/// precision flags given to the formatter are honoured, with the precision
+/// overriding [MoneyFormat::decimals]. As for other numbers, the `0` flag pads
+/// with zeros after the sign and symbol and ignores fill and alignment.
+///
+/// # Examples
+/// ```
+/// use postgres_money::{Money, MoneyFormat, NegativeStyle};
+///
+/// let format = MoneyFormat::new()
+///     .grouping(true)
+///     .negative_style(NegativeStyle::Parentheses);
+/// let money = Money::from(-123456789);
+///
+/// assert_eq!(format.format(money), "($1,234,567.89)");
+/// assert_eq!(format!("{:>16.0}", format.display(money)), "    ($1,234,568)");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MoneyFormat {
+    grouping: bool,
+    symbol: Option<Cow<'static, str>>,
+    negative_style: NegativeStyle,
+    decimals: usize,
+    rounding: Rounding,
+}
+
+impl Default for MoneyFormat {
+    fn default() -> Self {
+        MoneyFormat::new()
+    }
+}
+
+impl MoneyFormat {
+    pub(crate) const DISPLAY: MoneyFormat = MoneyFormat::new();
+
+    /// The format used by `Display`
+    pub const fn new() -> MoneyFormat {
+        MoneyFormat {
+            grouping: false,
+            symbol: Some(Cow::Borrowed("$")),
+            negative_style: NegativeStyle::Minus,
+            decimals: 2,
+            rounding: Rounding::HalfUp,
+        }
+    }
+
+    /// Separate thousands with commas
+    pub fn grouping(mut self, grouping: bool) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Show or hide the currency symbol
+    pub fn show_symbol(mut self, show: bool) -> Self {
+        self.symbol = match (show, self.symbol) {
+            (false, _) => None,
+            (true, None) => Some(Cow::Borrowed("$")),
+            (true, symbol) => symbol,
+        };
+        self
+    }
+
+    /// Use a currency symbol other than `$`
+    pub fn symbol(mut self, symbol: impl Into<Cow<'static, str>>) -> Self {
+        self.symbol = Some(symbol.into());
+        self
+    }
+
+    /// Mark negative amounts with `style`
+    pub fn negative_style(mut self, style: NegativeStyle) -> Self {
+        self.negative_style = style;
+        self
+    }
+
+    /// Show a fixed number of decimals, padding with zeros or rounding
+    /// according to [MoneyFormat::rounding]
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// How to round when showing fewer than two decimals, by default
+    /// [Rounding::HalfUp]
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// A value that displays `money` in this format
+    pub fn display(&self, money: Money) -> Formatted<'_> {
+        Formatted {
+            format: self,
+            money,
+        }
+    }
+
+    /// Format `money` as a String
+    pub fn format(&self, money: Money) -> String {
+        self.display(money).to_string()
+    }
+
+    pub(crate) fn fmt(&self, money: Money, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = f.precision().unwrap_or(self.decimals);
+        let rendered = Rendered::new(self, money, decimals, f.sign_plus());
+
+        let width = match f.width() {
+            Some(width) => width,
+            None => return rendered.write_to(f),
+        };
+        let mut counter = Counter(0);
+        rendered.write_to(&mut counter)?;
+        let padding = width.saturating_sub(counter.0);
+
+        if f.sign_aware_zero_pad() {
+            rendered.write_prefix(f)?;
+            for _ in 0..padding {
+                f.write_char('0')?;
+            }
+            return rendered.write_body(f);
+        }
+
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            // Right aligned like other numbers
+            Some(fmt::Alignment::Right) | None => (padding, 0),
+        };
+
+        let fill = f.fill();
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+        rendered.write_to(f)?;
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+        Ok(())
+    }
+}
+
+/// Money displayed in a [MoneyFormat]
+#[derive(Clone, Copy, Debug)]
+pub struct Formatted<'a> {
+    format: &'a MoneyFormat,
+    money: Money,
+}
+
+impl fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format.fmt(self.money, f)
+    }
+}
+
+/// An amount rounded to the decimals it is shown with
+struct Rendered<'a> {
+    format: &'a MoneyFormat,
+    negative: bool,
+    plus: bool,
+    /// The magnitude in units of the last decimal shown, or in cents when
+    /// showing more than two
+    units: u128,
+    decimals: usize,
+}
+
+impl<'a> Rendered<'a> {
+    fn new(format: &'a MoneyFormat, money: Money, decimals: usize, plus: bool) -> Self {
+        let cents = i128::from(money.inner());
+        let units = match decimals {
+            0 => format.rounding.div(cents, 100),
+            1 => format.rounding.div(cents, 10),
+            _ => cents,
+        };
+
+        Rendered {
+            format,
+            negative: units < 0,
+            plus,
+            units: units.unsigned_abs(),
+            decimals,
+        }
+    }
+
+    fn write_to<W: Write>(&self, w: &mut W) -> fmt::Result {
+        self.write_prefix(w)?;
+        self.write_body(w)
+    }
+
+    /// The sign and symbol, which zero padding goes after
+    fn write_prefix<W: Write>(&self, w: &mut W) -> fmt::Result {
+        if self.negative {
+            match self.format.negative_style {
+                NegativeStyle::Minus => w.write_char('-')?,
+                NegativeStyle::Parentheses => w.write_char('(')?,
+                NegativeStyle::TrailingMinus | NegativeStyle::CrDr => {}
+            }
+        } else if self.plus {
+            w.write_char('+')?;
+        }
+
+        match &self.format.symbol {
+            Some(symbol) => w.write_str(symbol),
+            None => Ok(()),
+        }
+    }
+
+    /// The digits and any trailing sign
+    fn write_body<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let scale = 10u128.pow(self.decimals.min(2) as u32);
+        self.write_integer(w, self.units / scale)?;
+
+        if self.decimals > 0 {
+            w.write_char('.')?;
+            let shown = self.decimals.min(2);
+            write!(w, "{:0shown$}", self.units % scale)?;
+            for _ in shown..self.decimals {
+                w.write_char('0')?;
+            }
+        }
+
+        match self.format.negative_style {
+            NegativeStyle::Parentheses if self.negative => w.write_char(')'),
+            NegativeStyle::TrailingMinus if self.negative => w.write_char('-'),
+            NegativeStyle::CrDr if self.negative => w.write_str(" CR"),
+            NegativeStyle::CrDr if self.units > 0 => w.write_str(" DR"),
+            _ => Ok(()),
+        }
+    }
+
+    fn write_integer<W: Write>(&self, w: &mut W, integer: u128) -> fmt::Result {
+        if !self.format.grouping || integer < 1000 {
+            return write!(w, "{integer}");
+        }
+
+        // Highest group first, then each lower group padded to three digits
+        let mut divisor = 1000;
+        while integer / divisor >= 1000 {
+            divisor *= 1000;
+        }
+        write!(w, "{}", integer / divisor)?;
+        while divisor > 1 {
+            divisor /= 1000;
+            write!(w, ",{:03}", integer / divisor % 1000)?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts the characters written to it
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_display_unchanged() {
+        assert_eq!(Money(-123456).to_string(), "-$1234.56");
+        assert_eq!(Money(5).to_string(), "$0.05");
+        assert_eq!(Money::min().to_string(), "-$92233720368547758.08");
+        assert_eq!(Money::max().to_string(), "$92233720368547758.07");
+    }
+
+    #[test]
+    fn test_display_flags() {
+        let money = Money(123456);
+        assert_eq!(format!("{money:>12}"), "    $1234.56");
+        assert_eq!(format!("{money:12}"), "    $1234.56");
+        assert_eq!(format!("{money:<12}|"), "$1234.56    |");
+        assert_eq!(format!("{money:*^12}"), "**$1234.56**");
+        assert_eq!(format!("{money:3}"), "$1234.56");
+        assert_eq!(format!("{money:+}"), "+$1234.56");
+        assert_eq!(format!("{:+}", Money(-123456)), "-$1234.56");
+        assert_eq!(format!("{money:.0}"), "$1235");
+        assert_eq!(format!("{money:.1}"), "$1234.6");
+        assert_eq!(format!("{money:.4}"), "$1234.5600");
+        assert_eq!(format!("{money:>+10.0}"), "    +$1235");
+    }
+
+    #[test]
+    fn test_display_zero_pad() {
+        assert_eq!(format!("{:010}", Money(-1234)), "-$00012.34");
+        assert_eq!(format!("{:+010}", Money(1234)), "+$00012.34");
+        assert_eq!(format!("{:<010.0}", Money(1234)), "$000000012");
+        assert_eq!(format!("{:04}", Money(-1234)), "-$12.34");
+
+        let format = MoneyFormat::new().negative_style(NegativeStyle::Parentheses);
+        assert_eq!(
+            format!("{:011}", format.display(Money(-1234))),
+            "($00012.34)"
+        );
+        let format = MoneyFormat::new().show_symbol(false);
+        assert_eq!(format!("{:08}", format.display(Money(-5))), "-0000.05");
+    }
+
+    #[test]
+    fn test_precision_rounding() {
+        assert_eq!(format!("{:.0}", Money(-150)), "-$2");
+        assert_eq!(format!("{:.0}", Money(-49)), "$0");
+        assert_eq!(format!("{:.1}", Money(-5)), "-$0.1");
+        assert_eq!(format!("{:.0}", Money::min()), "-$92233720368547758");
+        assert_eq!(
+            MoneyFormat::new()
+                .decimals(0)
+                .rounding(Rounding::Floor)
+                .format(Money(-101)),
+            "-$2"
+        );
+    }
+
+    #[test]
+    fn test_grouping() {
+        let format = MoneyFormat::new().grouping(true);
+        assert_eq!(format.format(Money(0)), "$0.00");
+        assert_eq!(format.format(Money(99999)), "$999.99");
+        assert_eq!(format.format(Money(100000)), "$1,000.00");
+        assert_eq!(format.format(Money(-100000001)), "-$1,000,000.01");
+        assert_eq!(format.format(Money::min()), "-$92,233,720,368,547,758.08");
+    }
+
+    #[test]
+    fn test_symbol() {
+        let money = Money(-150);
+        assert_eq!(MoneyFormat::new().show_symbol(false).format(money), "-1.50");
+        assert_eq!(MoneyFormat::new().symbol("€").format(money), "-€1.50");
+        assert_eq!(
+            MoneyFormat::new()
+                .symbol(String::from("US$"))
+                .show_symbol(true)
+                .format(money),
+            "-US$1.50"
+        );
+        assert_eq!(
+            format!("{:>8}", MoneyFormat::new().symbol("€").display(money)),
+            "  -€1.50"
+        );
+    }
+
+    #[test]
+    fn test_trailing_minus_and_cr_dr() {
+        let format = MoneyFormat::new().negative_style(NegativeStyle::TrailingMinus);
+        assert_eq!(format.format(Money(-150)), "$1.50-");
+        assert_eq!(format.format(Money(150)), "$1.50");
+
+        let format = MoneyFormat::new().negative_style(NegativeStyle::CrDr);
+        assert_eq!(format.format(Money(-150)), "$1.50 CR");
+        assert_eq!(format.format(Money(150)), "$1.50 DR");
+        assert_eq!(format.format(Money(0)), "$0.00");
+        assert_eq!(format!("{:>10}", format.display(Money(-1))), "  $0.01 CR");
+    }
+
+    const STYLES: [NegativeStyle; 4] = [
+        NegativeStyle::Minus,
+        NegativeStyle::Parentheses,
+        NegativeStyle::TrailingMinus,
+        NegativeStyle::CrDr,
+    ];
+
+    proptest! {
+        #[test]
+        fn prop_negative_styles_round_trip(
+            inner in any::<i64>(),
+            style in prop::sample::select(&STYLES[..]),
+            grouping in any::<bool>(),
+        ) {
+            let money = Money(inner);
+            let format = MoneyFormat::new().negative_style(style).grouping(grouping);
+            prop_assert_eq!(Money::parse_str(&format.format(money)), Ok(money));
+        }
+
+        #[test]
+        fn prop_display_round_trip(inner in any::<i64>()) {
+            let money = Money(inner);
+            prop_assert_eq!(Money::parse_str(&money.to_string()), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_negative_styles_round_trip_extremes() {
+        for style in STYLES {
+            let format = MoneyFormat::new().negative_style(style).grouping(true);
+            for money in [Money::min(), Money(-1), Money(0), Money::max()] {
+                assert_eq!(Money::parse_str(&format.format(money)), Ok(money));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parentheses() {
+        let format = MoneyFormat::new().negative_style(NegativeStyle::Parentheses);
+        assert_eq!(format.format(Money(-150)), "($1.50)");
+        assert_eq!(format.format(Money(150)), "$1.50");
+        assert_eq!(format!("{:+}", format.display(Money(150))), "+$1.50");
+        assert_eq!(format!("{:.0}", format.display(Money(-40))), "$0");
+    }
+}
@@ -0,0 +1,833 @@
+//! In-memory double-entry bookkeeping.
+//!
+//! A [Ledger] holds [Transaction]s, each a set of [Posting]s whose debits
+//! equal their credits. Accounts form a hierarchy through colon-separated
+//! paths such as `Assets:Bank:Checking`, which [Ledger::balance_sheet] rolls
+//! up into totals for every parent account. Every account in a hierarchy
+//! shares the kind of its top-level account.
+//!
+//! All sums use checked arithmetic and fail with `Error::OutOfRange` rather
+//! than wrapping.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::ledger::{Account, AccountKind, Ledger, Posting, Transaction};
+//! use postgres_money::{Error, Money};
+//!
+//! let checking = Account::new("Assets:Bank:Checking", AccountKind::Asset).unwrap();
+//! let capital = Account::new("Equity:Capital", AccountKind::Equity).unwrap();
+//! let rent = Account::new("Expenses:Rent", AccountKind::Expense).unwrap();
+//!
+//! let mut ledger = Ledger::new();
+//! ledger.post(Transaction::new("Owner investment", vec![
+//!     Posting::debit(checking.clone(), Money::from(500_000)),
+//!     Posting::credit(capital, Money::from(500_000)),
+//! ]).unwrap()).unwrap();
+//! ledger.post(Transaction::new("March rent", vec![
+//!     Posting::debit(rent.clone(), Money::from(120_000)),
+//!     Posting::credit(checking.clone(), Money::from(120_000)),
+//! ]).unwrap()).unwrap();
+//!
+//! // Debits must equal credits
+//! assert_eq!(
+//!     Transaction::new("Typo", vec![
+//!         Posting::debit(rent, Money::from(100)),
+//!         Posting::credit(checking, Money::from(10)),
+//!     ]).unwrap_err(),
+//!     Error::Unbalanced
+//! );
+//!
+//! let sheet = ledger.balance_sheet().unwrap();
+//! assert_eq!(sheet.assets[0].path, "Assets");
+//! assert_eq!(sheet.assets[0].balance, Money::from(380_000));
+//! assert_eq!(sheet.net_income, Money::from(-120_000));
+//! assert_eq!(sheet.total_assets(), sheet.total_liabilities_and_equity());
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use crate::{Error, Money};
+
+/// Separates the levels of an account path
+pub const SEPARATOR: char = ':';
+
+/// The five classes of account in the accounting equation
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AccountKind {
+    /// Resources owned, increased by debits
+    Asset,
+    /// Amounts owed, increased by credits
+    Liability,
+    /// The owners' stake, increased by credits
+    Equity,
+    /// Income, increased by credits
+    Revenue,
+    /// Costs, increased by debits
+    Expense,
+}
+
+impl AccountKind {
+    /// Whether debits increase accounts of this kind
+    pub const fn is_debit_normal(self) -> bool {
+        matches!(self, AccountKind::Asset | AccountKind::Expense)
+    }
+}
+
+/// An account, identified by its path and kind
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Account {
+    path: String,
+    kind: AccountKind,
+}
+
+impl Account {
+    /// Construct an account from a path such as `Assets:Bank:Checking`.
+    ///
+    /// Returns `Error::InvalidArgument` if the path or any level of it is
+    /// empty.
+    pub fn new(path: impl Into<String>, kind: AccountKind) -> Result<Account, Error> {
+        let path = path.into();
+        if path.split(SEPARATOR).any(str::is_empty) {
+            return Err(Error::InvalidArgument);
+        }
+        Ok(Account { path, kind })
+    }
+
+    /// The full path of the account
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The kind of the account
+    pub fn kind(&self) -> AccountKind {
+        self.kind
+    }
+
+    /// The last level of the path
+    pub fn name(&self) -> &str {
+        self.path.rsplit(SEPARATOR).next().unwrap_or_default()
+    }
+
+    /// The path of the parent account, or `None` at the top level
+    pub fn parent(&self) -> Option<&str> {
+        self.path.rsplit_once(SEPARATOR).map(|(parent, _)| parent)
+    }
+}
+
+/// Which side of an account a posting is entered on
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Entry {
+    /// A debit of the amount
+    Debit(Money),
+    /// A credit of the amount
+    Credit(Money),
+}
+
+/// A single debit or credit to an account
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Posting {
+    /// The account posted to
+    pub account: Account,
+    /// The side and amount of the posting
+    pub entry: Entry,
+}
+
+impl Posting {
+    /// Debit `amount` to `account`
+    pub fn debit(account: Account, amount: Money) -> Posting {
+        Posting {
+            account,
+            entry: Entry::Debit(amount),
+        }
+    }
+
+    /// Credit `amount` to `account`
+    pub fn credit(account: Account, amount: Money) -> Posting {
+        Posting {
+            account,
+            entry: Entry::Credit(amount),
+        }
+    }
+}
+
+/// A balanced set of postings
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Transaction {
+    description: String,
+    postings: Vec<Posting>,
+    total: Money,
+}
+
+impl Transaction {
+    /// Construct a transaction, checking that its debits equal its credits.
+    ///
+    /// Returns `Error::InvalidArgument` if there are no postings, any amount
+    /// is negative or every amount is zero, `Error::OutOfRange` if the debits
+    /// or credits overflow, and `Error::Unbalanced` if they differ.
+    pub fn new(
+        description: impl Into<String>,
+        postings: Vec<Posting>,
+    ) -> Result<Transaction, Error> {
+        if postings.is_empty() {
+            return Err(Error::InvalidArgument);
+        }
+
+        let mut debits = Money::none();
+        let mut credits = Money::none();
+        for posting in &postings {
+            let (total, amount) = match posting.entry {
+                Entry::Debit(amount) => (&mut debits, amount),
+                Entry::Credit(amount) => (&mut credits, amount),
+            };
+            if amount < Money::none() {
+                return Err(Error::InvalidArgument);
+            }
+            *total = total.checked_add(amount).ok_or(Error::OutOfRange)?;
+        }
+
+        if debits != credits {
+            return Err(Error::Unbalanced);
+        }
+        if debits == Money::none() {
+            return Err(Error::InvalidArgument);
+        }
+
+        Ok(Transaction {
+            description: description.into(),
+            postings,
+            total: debits,
+        })
+    }
+
+    /// The description given when the transaction was made
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Every posting in the order given
+    pub fn postings(&self) -> &[Posting] {
+        &self.postings
+    }
+
+    /// The sum of the debits, which is also the sum of the credits
+    pub fn total(&self) -> Money {
+        self.total
+    }
+}
+
+/// Debit and credit totals for one account
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TrialBalanceLine {
+    /// The account totalled
+    pub account: Account,
+    /// The net balance if it is a debit, otherwise zero
+    pub debit: Money,
+    /// The net balance if it is a credit, otherwise zero
+    pub credit: Money,
+}
+
+impl TrialBalanceLine {
+    /// The balance with the sign of the account's normal side, so that a
+    /// positive balance increases an asset or a liability alike
+    pub fn balance(&self) -> Result<Money, Error> {
+        let (plus, minus) = if self.account.kind.is_debit_normal() {
+            (self.debit, self.credit)
+        } else {
+            (self.credit, self.debit)
+        };
+        plus.checked_sub(minus).ok_or(Error::OutOfRange)
+    }
+}
+
+/// The net balance of every account, in path order
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TrialBalance {
+    /// One line for each account posted to
+    pub lines: Vec<TrialBalanceLine>,
+    /// The sum of the debit column
+    pub total_debits: Money,
+    /// The sum of the credit column
+    pub total_credits: Money,
+}
+
+impl TrialBalance {
+    /// Whether the debit and credit columns agree, as they do for any ledger
+    /// of balanced transactions
+    pub fn is_balanced(&self) -> bool {
+        self.total_debits == self.total_credits
+    }
+}
+
+/// An account and the total of everything beneath it
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Rollup {
+    /// The full path of the account
+    pub path: String,
+    /// The balance of the account and all its descendants
+    pub balance: Money,
+    /// The accounts directly beneath this one, in path order
+    pub children: Vec<Rollup>,
+}
+
+/// Balances rolled up through the account hierarchy.
+///
+/// Each section holds the top-level accounts of its kind, and each balance
+/// has the sign of its kind's normal side. Revenue and expenses are not
+/// listed but close into equity as `net_income`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BalanceSheet {
+    /// The asset accounts
+    pub assets: Vec<Rollup>,
+    /// The liability accounts
+    pub liabilities: Vec<Rollup>,
+    /// The equity accounts
+    pub equity: Vec<Rollup>,
+    /// Revenue less expenses
+    pub net_income: Money,
+}
+
+impl BalanceSheet {
+    /// The sum of the asset accounts
+    pub fn total_assets(&self) -> Result<Money, Error> {
+        section_total(&self.assets)
+    }
+
+    /// The sum of the liability accounts
+    pub fn total_liabilities(&self) -> Result<Money, Error> {
+        section_total(&self.liabilities)
+    }
+
+    /// The sum of the equity accounts, including net income
+    pub fn total_equity(&self) -> Result<Money, Error> {
+        section_total(&self.equity)?
+            .checked_add(self.net_income)
+            .ok_or(Error::OutOfRange)
+    }
+
+    /// Liabilities plus equity, which equals the assets for any ledger of
+    /// balanced transactions
+    pub fn total_liabilities_and_equity(&self) -> Result<Money, Error> {
+        self.total_equity()?
+            .checked_add(self.total_liabilities()?)
+            .ok_or(Error::OutOfRange)
+    }
+}
+
+fn section_total(section: &[Rollup]) -> Result<Money, Error> {
+    crate::stats::sum(section.iter().map(|rollup| rollup.balance))
+}
+
+/// A collection of balanced transactions
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Ledger {
+    transactions: Vec<Transaction>,
+    /// The kind of every account path posted to and of each of its ancestors
+    kinds: BTreeMap<String, AccountKind>,
+}
+
+impl Ledger {
+    /// An empty ledger
+    pub fn new() -> Ledger {
+        Ledger::default()
+    }
+
+    /// Record a transaction.
+    ///
+    /// Returns `Error::AccountKindMismatch`, and records nothing, if any
+    /// posting's account or one of its ancestors was already used with a
+    /// different kind.
+    pub fn post(&mut self, transaction: Transaction) -> Result<(), Error> {
+        let mut added: BTreeMap<&str, AccountKind> = BTreeMap::new();
+        for account in transaction.postings.iter().map(|p| &p.account) {
+            let mut path = account.path();
+            loop {
+                let known = self.kinds.get(path).or_else(|| added.get(path));
+                match known {
+                    Some(&kind) if kind != account.kind => return Err(Error::AccountKindMismatch),
+                    Some(_) => {}
+                    None => {
+                        added.insert(path, account.kind);
+                    }
+                }
+                match path.rsplit_once(SEPARATOR) {
+                    Some((parent, _)) => path = parent,
+                    None => break,
+                }
+            }
+        }
+
+        for (path, kind) in added {
+            self.kinds.insert(path.to_string(), kind);
+        }
+        self.transactions.push(transaction);
+        Ok(())
+    }
+
+    /// Every transaction in the order posted
+    pub fn transactions(&self) -> &[Transaction] {
+        &self.transactions
+    }
+
+    /// Net the postings to each account into a debit or credit balance.
+    ///
+    /// Returns `Error::OutOfRange` if any total overflows.
+    pub fn trial_balance(&self) -> Result<TrialBalance, Error> {
+        // Keyed by path alone, since post keeps one kind for each path
+        let mut nets: BTreeMap<&str, (&Account, Money)> = BTreeMap::new();
+        for posting in self.transactions.iter().flat_map(|t| &t.postings) {
+            let (_, net) = nets
+                .entry(posting.account.path())
+                .or_insert((&posting.account, Money::none()));
+            *net = match posting.entry {
+                Entry::Debit(amount) => net.checked_add(amount),
+                Entry::Credit(amount) => net.checked_sub(amount),
+            }
+            .ok_or(Error::OutOfRange)?;
+        }
+
+        let mut trial = TrialBalance::default();
+        for (account, net) in nets.into_values() {
+            let (debit, credit) = if net >= Money::none() {
+                (net, Money::none())
+            } else {
+                let credit = Money::none().checked_sub(net).ok_or(Error::OutOfRange)?;
+                (Money::none(), credit)
+            };
+
+            trial.total_debits = trial
+                .total_debits
+                .checked_add(debit)
+                .ok_or(Error::OutOfRange)?;
+            trial.total_credits = trial
+                .total_credits
+                .checked_add(credit)
+                .ok_or(Error::OutOfRange)?;
+            trial.lines.push(TrialBalanceLine {
+                account: account.clone(),
+                debit,
+                credit,
+            });
+        }
+        Ok(trial)
+    }
+
+    /// Roll the trial balance up the account hierarchy.
+    ///
+    /// Parent accounts need not be posted to themselves; their balance is
+    /// whatever they hold directly plus the total of their children. Returns
+    /// `Error::OutOfRange` if any total overflows.
+    pub fn balance_sheet(&self) -> Result<BalanceSheet, Error> {
+        let mut sections: BTreeMap<AccountKind, BTreeMap<&str, Money>> = BTreeMap::new();
+        let trial = self.trial_balance()?;
+        for line in &trial.lines {
+            let kind = line.account.kind;
+            let balances = sections.entry(kind).or_default();
+            let mut path = line.account.path();
+            let balance = line.balance()?;
+            loop {
+                let total = balances.entry(path).or_default();
+                *total = total.checked_add(balance).ok_or(Error::OutOfRange)?;
+                match path.rsplit_once(SEPARATOR) {
+                    Some((parent, _)) => path = parent,
+                    None => break,
+                }
+            }
+        }
+
+        let mut section = |kind| {
+            sections
+                .remove(&kind)
+                .map(|balances| rollups(&balances, None))
+                .unwrap_or_default()
+        };
+        let assets = section(AccountKind::Asset);
+        let liabilities = section(AccountKind::Liability);
+        let equity = section(AccountKind::Equity);
+        let net_income = section_total(&section(AccountKind::Revenue))?
+            .checked_sub(section_total(&section(AccountKind::Expense))?)
+            .ok_or(Error::OutOfRange)?;
+
+        Ok(BalanceSheet {
+            assets,
+            liabilities,
+            equity,
+            net_income,
+        })
+    }
+}
+
+/// Build the subtree of every account directly beneath `parent`, or of the
+/// top-level accounts if it is `None`
+fn rollups(balances: &BTreeMap<&str, Money>, parent: Option<&str>) -> Vec<Rollup> {
+    let rollup = |(&path, &balance): (&&str, &Money)| Rollup {
+        path: path.to_string(),
+        balance,
+        children: rollups(balances, Some(path)),
+    };
+
+    match parent {
+        None => balances
+            .iter()
+            .filter(|(path, _)| !path.contains(SEPARATOR))
+            .map(rollup)
+            .collect(),
+        // Descendants share the `parent:` prefix, so sort together after it
+        Some(parent) => {
+            let prefix = format!("{parent}{SEPARATOR}");
+            balances
+                .range::<str, _>((Bound::Included(prefix.as_str()), Bound::Unbounded))
+                .take_while(|(path, _)| path.starts_with(&prefix))
+                .filter(|(path, _)| !path[prefix.len()..].contains(SEPARATOR))
+                .map(rollup)
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(path: &str, kind: AccountKind) -> Account {
+        Account::new(path, kind).unwrap()
+    }
+
+    fn transaction(postings: Vec<Posting>) -> Transaction {
+        Transaction::new("test", postings).unwrap()
+    }
+
+    fn sample() -> Ledger {
+        let checking = account("Assets:Bank:Checking", AccountKind::Asset);
+        let savings = account("Assets:Bank:Savings", AccountKind::Asset);
+        let cash = account("Assets:Cash", AccountKind::Asset);
+        let card = account("Liabilities:Card", AccountKind::Liability);
+        let capital = account("Equity:Capital", AccountKind::Equity);
+        let sales = account("Revenue:Sales", AccountKind::Revenue);
+        let supplies = account("Expenses:Supplies", AccountKind::Expense);
+
+        let mut ledger = Ledger::new();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(checking.clone(), Money(100_000)),
+                Posting::credit(capital, Money(100_000)),
+            ]))
+            .unwrap();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(savings, Money(30_000)),
+                Posting::credit(checking.clone(), Money(30_000)),
+            ]))
+            .unwrap();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(cash, Money(5_000)),
+                Posting::debit(checking, Money(15_000)),
+                Posting::credit(sales, Money(20_000)),
+            ]))
+            .unwrap();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(supplies, Money(4_000)),
+                Posting::credit(card, Money(4_000)),
+            ]))
+            .unwrap();
+        ledger
+    }
+
+    #[test]
+    fn test_account_paths() {
+        let checking = account("Assets:Bank:Checking", AccountKind::Asset);
+        assert_eq!(checking.name(), "Checking");
+        assert_eq!(checking.parent(), Some("Assets:Bank"));
+        assert_eq!(account("Assets", AccountKind::Asset).parent(), None);
+
+        for path in ["", "Assets:", ":Cash", "Assets::Cash"] {
+            assert_eq!(
+                Account::new(path, AccountKind::Asset),
+                Err(Error::InvalidArgument)
+            );
+        }
+    }
+
+    #[test]
+    fn test_transaction_validation() {
+        let cash = account("Assets:Cash", AccountKind::Asset);
+        let sales = account("Revenue:Sales", AccountKind::Revenue);
+
+        assert_eq!(
+            Transaction::new("empty", vec![]),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Transaction::new(
+                "negative",
+                vec![
+                    Posting::debit(cash.clone(), Money(-5)),
+                    Posting::credit(sales.clone(), Money(-5)),
+                ]
+            ),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Transaction::new(
+                "unbalanced",
+                vec![
+                    Posting::debit(cash.clone(), Money(5)),
+                    Posting::credit(sales.clone(), Money(4)),
+                ]
+            ),
+            Err(Error::Unbalanced)
+        );
+        assert_eq!(
+            Transaction::new(
+                "overflow",
+                vec![
+                    Posting::debit(cash.clone(), Money::max()),
+                    Posting::debit(cash.clone(), Money(1)),
+                    Posting::credit(sales.clone(), Money::max()),
+                ]
+            ),
+            Err(Error::OutOfRange)
+        );
+
+        assert_eq!(
+            Transaction::new("zero", vec![Posting::debit(cash.clone(), Money(0))]),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Transaction::new(
+                "zeros",
+                vec![
+                    Posting::debit(cash.clone(), Money(0)),
+                    Posting::credit(sales.clone(), Money(0)),
+                ]
+            ),
+            Err(Error::InvalidArgument)
+        );
+
+        let split = transaction(vec![
+            Posting::debit(cash.clone(), Money(300)),
+            Posting::debit(cash, Money(200)),
+            Posting::credit(sales, Money(500)),
+        ]);
+        assert_eq!(split.total(), Money(500));
+        assert_eq!(split.description(), "test");
+        assert_eq!(split.postings().len(), 3);
+    }
+
+    #[test]
+    fn test_trial_balance() {
+        let trial = sample().trial_balance().unwrap();
+        let lines: Vec<_> = trial
+            .lines
+            .iter()
+            .map(|l| (l.account.path(), l.debit.0, l.credit.0))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("Assets:Bank:Checking", 85_000, 0),
+                ("Assets:Bank:Savings", 30_000, 0),
+                ("Assets:Cash", 5_000, 0),
+                ("Equity:Capital", 0, 100_000),
+                ("Expenses:Supplies", 4_000, 0),
+                ("Liabilities:Card", 0, 4_000),
+                ("Revenue:Sales", 0, 20_000),
+            ]
+        );
+        assert_eq!(trial.total_debits, Money(124_000));
+        assert!(trial.is_balanced());
+        assert_eq!(trial.lines[5].balance(), Ok(Money(4_000)));
+    }
+
+    #[test]
+    fn test_trial_balance_of_empty_ledger() {
+        let trial = Ledger::new().trial_balance().unwrap();
+        assert!(trial.lines.is_empty());
+        assert!(trial.is_balanced());
+    }
+
+    #[test]
+    fn test_balance_sheet() {
+        let sheet = sample().balance_sheet().unwrap();
+
+        let bank = Rollup {
+            path: "Assets:Bank".to_string(),
+            balance: Money(115_000),
+            children: vec![
+                Rollup {
+                    path: "Assets:Bank:Checking".to_string(),
+                    balance: Money(85_000),
+                    children: vec![],
+                },
+                Rollup {
+                    path: "Assets:Bank:Savings".to_string(),
+                    balance: Money(30_000),
+                    children: vec![],
+                },
+            ],
+        };
+        assert_eq!(sheet.assets.len(), 1);
+        assert_eq!(sheet.assets[0].path, "Assets");
+        assert_eq!(sheet.assets[0].balance, Money(120_000));
+        assert_eq!(sheet.assets[0].children[0], bank);
+        assert_eq!(sheet.assets[0].children[1].path, "Assets:Cash");
+
+        assert_eq!(sheet.total_assets(), Ok(Money(120_000)));
+        assert_eq!(sheet.total_liabilities(), Ok(Money(4_000)));
+        assert_eq!(sheet.net_income, Money(16_000));
+        assert_eq!(sheet.total_equity(), Ok(Money(116_000)));
+        assert_eq!(sheet.total_liabilities_and_equity(), Ok(Money(120_000)));
+    }
+
+    #[test]
+    fn test_balance_sheet_parent_postings() {
+        let bank = account("Assets:Bank", AccountKind::Asset);
+        let checking = account("Assets:Bank:Checking", AccountKind::Asset);
+        let loan = account("Liabilities:Loan", AccountKind::Liability);
+
+        let mut ledger = Ledger::new();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(bank.clone(), Money(700)),
+                Posting::debit(checking, Money(300)),
+                Posting::credit(loan, Money(1_000)),
+            ]))
+            .unwrap();
+
+        let sheet = ledger.balance_sheet().unwrap();
+        let bank = &sheet.assets[0].children[0];
+        assert_eq!(bank.path, "Assets:Bank");
+        assert_eq!(bank.balance, Money(1_000));
+        assert_eq!(bank.children[0].balance, Money(300));
+        assert_eq!(sheet.liabilities[0].balance, Money(1_000));
+        assert!(sheet.equity.is_empty());
+    }
+
+    #[test]
+    fn test_rollup_ignores_sibling_prefixes() {
+        // "Assets:Bank 2" sorts between "Assets:Bank" and "Assets:Bank:Checking"
+        let mut ledger = Ledger::new();
+        for path in ["Assets:Bank:Checking", "Assets:Bank 2", "Assets:Bank2:Cash"] {
+            ledger
+                .post(transaction(vec![
+                    Posting::debit(account(path, AccountKind::Asset), Money(100)),
+                    Posting::credit(account("Equity:Capital", AccountKind::Equity), Money(100)),
+                ]))
+                .unwrap();
+        }
+
+        let sheet = ledger.balance_sheet().unwrap();
+        let assets = &sheet.assets[0];
+        let paths: Vec<_> = assets.children.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["Assets:Bank", "Assets:Bank 2", "Assets:Bank2"]);
+        assert_eq!(assets.balance, Money(300));
+        assert_eq!(assets.children[0].balance, Money(100));
+        assert_eq!(assets.children[0].children.len(), 1);
+        assert_eq!(assets.children[2].children[0].path, "Assets:Bank2:Cash");
+    }
+
+    #[test]
+    fn test_several_top_level_accounts() {
+        let mut ledger = Ledger::new();
+        for path in ["Cash", "Bank"] {
+            ledger
+                .post(transaction(vec![
+                    Posting::debit(account(path, AccountKind::Asset), Money(100)),
+                    Posting::credit(
+                        account(&format!("Equity:{path}"), AccountKind::Equity),
+                        Money(100),
+                    ),
+                ]))
+                .unwrap();
+        }
+
+        let sheet = ledger.balance_sheet().unwrap();
+        let paths: Vec<_> = sheet.assets.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["Bank", "Cash"]);
+        assert_eq!(sheet.total_assets(), Ok(Money(200)));
+        assert_eq!(sheet.total_equity(), Ok(Money(200)));
+    }
+
+    #[test]
+    fn test_conflicting_kinds_rejected() {
+        let cash = account("Assets:Cash", AccountKind::Asset);
+        let capital = account("Equity:Capital", AccountKind::Equity);
+
+        let mut ledger = Ledger::new();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(cash.clone(), Money(100)),
+                Posting::credit(capital.clone(), Money(100)),
+            ]))
+            .unwrap();
+
+        // The same path, a sibling and a parent of a known account
+        for conflict in ["Assets:Cash", "Assets:Loan", "Assets"] {
+            let conflict = account(conflict, AccountKind::Liability);
+            assert_eq!(
+                ledger.post(transaction(vec![
+                    Posting::debit(cash.clone(), Money(100)),
+                    Posting::credit(conflict, Money(100)),
+                ])),
+                Err(Error::AccountKindMismatch)
+            );
+        }
+
+        // A conflict within one transaction
+        assert_eq!(
+            Ledger::new().post(transaction(vec![
+                Posting::debit(account("Cash", AccountKind::Asset), Money(100)),
+                Posting::credit(account("Cash", AccountKind::Equity), Money(100)),
+            ])),
+            Err(Error::AccountKindMismatch)
+        );
+
+        // Rejected transactions leave the ledger untouched
+        assert_eq!(ledger.transactions().len(), 1);
+        let trial = ledger.trial_balance().unwrap();
+        let paths: Vec<_> = trial.lines.iter().map(|l| l.account.path()).collect();
+        assert_eq!(paths, ["Assets:Cash", "Equity:Capital"]);
+        ledger
+            .post(transaction(vec![
+                Posting::debit(account("Assets:Bank", AccountKind::Asset), Money(5)),
+                Posting::credit(capital, Money(5)),
+            ]))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_overflowing_totals() {
+        let a = account("Assets:A", AccountKind::Asset);
+        let b = account("Assets:B", AccountKind::Asset);
+        let equity = account("Equity:Capital", AccountKind::Equity);
+        let debt = account("Liabilities:Debt", AccountKind::Liability);
+
+        let mut ledger = Ledger::new();
+        for asset in [a, b] {
+            ledger
+                .post(transaction(vec![
+                    Posting::debit(asset, Money::max()),
+                    Posting::credit(equity.clone(), Money::max()),
+                ]))
+                .unwrap();
+        }
+        assert_eq!(ledger.trial_balance(), Err(Error::OutOfRange));
+
+        let mut ledger = Ledger::new();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(account("Assets:A", AccountKind::Asset), Money::max()),
+                Posting::credit(debt, Money::max()),
+            ]))
+            .unwrap();
+        ledger
+            .post(transaction(vec![
+                Posting::debit(account("Assets:B", AccountKind::Asset), Money::max()),
+                Posting::credit(equity, Money::max()),
+            ]))
+            .unwrap();
+        assert_eq!(ledger.balance_sheet(), Err(Error::OutOfRange));
+    }
+}
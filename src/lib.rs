@@ -5,7 +5,7 @@
 
 //! # Dependencies
 //!
-//! By default, this crate depends on the `regex` crate.
+//! By default, this crate has no dependencies.
 //!
 //! To activate JSON serialization via the `serde` crate, use syntax like:
 //! ```toml
@@ -13,27 +13,105 @@
 //! postgres_money = { version = "0.4.1", features = ["serde", "sql"] }
 //! ```
 //!
+//! The `sql` feature implements `FromSql`/`ToSql` from `postgres-types`, and
+//! the `diesel` feature adds a Diesel [sql_types::Money] type along with the
+//! matching `FromSql`/`ToSql` implementations. The `sqlx` feature implements
+//! SQLx's `Type`, `Encode` and `Decode` for `Money` and `money[]`. The
+//! `sea-orm` feature lets SeaORM entities declare `Money` fields on `MONEY`
+//! columns. Each of these also handles [BoundedMoney], which rejects values
+//! outside its bounds on decode, as serde does.
+//!
+//! The `pgrx` feature passes `Money` into and out of Postgres extension
+//! functions as the built-in `money` type. Enable one of pgrx's `pg13`..`pg18`
+//! features alongside it, as for any pgrx extension.
+//!
+//! The `arrow` feature adds the [arrow] module for converting Money columns
+//! to and from Arrow arrays, and so Parquet files. The `csv` feature adds the
+//! [csv] module of serde helpers and a column reader for CSV files. The
+//! `prost` feature adds the [proto] module for the protobuf
+//! `google.type.Money` message.
+//!
+//! The `rust_decimal` and `bigdecimal` features convert exactly between Money
+//! and those crates' decimal types.
+//!
+//! For property tests, the `arbitrary` feature implements `Arbitrary` for
+//! fuzzers, and the `proptest` feature adds the [proptest] module of
+//! strategies. Both favour boundaries like [Money::min] over uniform draws.
+//!
 //! Visit the docs for [Money](struct.Money.html) for more info.
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+mod bounded;
+pub mod compact;
+mod convert;
+#[cfg(feature = "csv")]
+pub mod csv;
 mod error;
+pub mod finance;
+mod format;
+pub mod ledger;
 mod parser;
-
+mod parts;
+pub mod pricing;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "prost")]
+pub mod proto;
+mod rate;
+mod rounding;
+pub mod stats;
+pub mod tax;
+pub mod words;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal_impl;
+#[cfg(any(feature = "sql", feature = "diesel", feature = "sqlx"))]
+mod codec;
+#[cfg(feature = "diesel")]
+mod diesel_impl;
+#[cfg(feature = "pgrx")]
+mod pgrx_impl;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_impl;
+#[cfg(feature = "sea-orm")]
+mod sea_orm_impl;
 #[cfg(feature = "sql")]
 mod sql_impl;
-
-use error::Error;
+#[cfg(feature = "sqlx")]
+mod sqlx_impl;
+
+#[cfg(feature = "diesel")]
+pub use diesel_impl::{
+    money_sum, sql_types, MoneyAdd, MoneyExpressionMethods, MoneySqlType, MoneySub,
+};
+
+pub use bounded::{BoundedMoney, NonNegativeMoney, PositiveMoney};
+pub use error::Error;
+pub use format::{Formatted, MoneyFormat, NegativeStyle};
+pub use parts::MoneyParts;
+pub use rate::{BasisPoints, Percent, Rate};
+pub use rounding::Rounding;
 use std::ops::{Add, Div, Mul, Sub};
 use std::{fmt, str};
 
 /// Representation of the Postgres 'money' type
 #[derive(Clone, Copy, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = crate::sql_types::Money),
+    diesel(sql_type = diesel::sql_types::Money)
+)]
 pub struct Money(Inner);
 type Inner = i64;
 
 impl Money {
-    const MIN_INNER: Inner = std::i64::MIN;
-    const MAX_INNER: Inner = std::i64::MAX;
+    const MIN_INNER: Inner = i64::MIN;
+    const MAX_INNER: Inner = i64::MAX;
 
     /// Minimum allowable value for Money
     pub const fn min() -> Money {
@@ -45,6 +123,21 @@ impl Money {
         Money(Money::MAX_INNER)
     }
 
+    /// Values that generators for property tests weight heavily
+    #[cfg(any(feature = "arbitrary", feature = "proptest"))]
+    pub(crate) const BOUNDARIES: [Money; 10] = [
+        Money(Money::MIN_INNER),
+        Money(Money::MIN_INNER + 1),
+        Money(-100),
+        Money(-1),
+        Money(0),
+        Money(1),
+        Money(99),
+        Money(100),
+        Money(Money::MAX_INNER - 1),
+        Money(Money::MAX_INNER),
+    ];
+
     /// Instantiate Money as zero
     pub const fn none() -> Money {
         Money(0)
@@ -55,38 +148,40 @@ impl Money {
         self.0
     }
 
-    fn dollars(&self) -> String {
-        format!("{}", (self.0 / 100).abs())
+    /// Add two amounts, returning `None` on overflow
+    pub const fn checked_add(self, rhs: Money) -> Option<Money> {
+        match self.0.checked_add(rhs.0) {
+            Some(inner) => Some(Money(inner)),
+            None => None,
+        }
     }
 
-    fn cents(&self) -> String {
-        let n = (self.0 % 100).abs();
-        let mut zero_pad = "";
-        if n < 10 {
-            zero_pad = "0"
+    /// Subtract two amounts, returning `None` on overflow
+    pub const fn checked_sub(self, rhs: Money) -> Option<Money> {
+        match self.0.checked_sub(rhs.0) {
+            Some(inner) => Some(Money(inner)),
+            None => None,
         }
-        format!("{}{}", zero_pad, n)
     }
 
-    fn sign(&self) -> &str {
-        if self.inner() < 0 {
-            "-"
-        } else {
-            ""
-        }
+    /// Narrow a widened intermediate result back into Money
+    pub(crate) fn from_i128(inner: i128) -> Result<Money, Error> {
+        Inner::try_from(inner)
+            .map(Money)
+            .map_err(|_| Error::OutOfRange)
     }
 }
 
 impl fmt::Debug for Money {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self)
+        fmt::Display::fmt(self, f)
     }
 }
 
 impl fmt::Display for Money {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}${}.{}", self.sign(), self.dollars(), self.cents())
+        MoneyFormat::DISPLAY.fmt(*self, f)
     }
 }
 
@@ -348,6 +443,18 @@ mod tests {
         Money::min() + Money(-1);
     }
 
+    #[test]
+    fn test_checked_addition() {
+        assert_eq!(Money(1).checked_add(Money(1)), Some(Money(2)));
+        assert_eq!(Money::max().checked_add(Money(1)), None);
+    }
+
+    #[test]
+    fn test_checked_subtraction() {
+        assert_eq!(Money(2).checked_sub(Money(1)), Some(Money(1)));
+        assert_eq!(Money::min().checked_sub(Money(1)), None);
+    }
+
     #[test]
     fn test_subtraction_success() {
         assert_eq!(Money(2) - Money(1), Money(1))
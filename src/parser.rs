@@ -1,5 +1,3 @@
-use regex::{Match, Regex};
-
 /// #[doc(inline)]
 pub use crate::error::Error;
 
@@ -55,6 +53,16 @@ impl Money {
     /// assert_eq!("-$93.32", money.to_string());
     /// ```
     ///
+    /// Handles a trailing minus and accounting `CR`/`DR` suffixes, which mark
+    /// credits as negative and debits as positive
+    /// ```
+    /// use postgres_money::Money;
+    ///
+    /// assert_eq!("-$93.32", Money::parse_str("$93.32-").unwrap().to_string());
+    /// assert_eq!("-$93.32", Money::parse_str("$93.32 CR").unwrap().to_string());
+    /// assert_eq!("$93.32", Money::parse_str("$93.32 DR").unwrap().to_string());
+    /// ```
+    ///
     /// Handles dollar symbols
     /// ```
     /// use postgres_money::Money;
@@ -106,155 +114,211 @@ impl Money {
     /// assert_eq!("-$92233720368547758.08", money.to_string());
     /// assert_eq!(Money::min().to_string(), money.to_string());
     /// ```
-    pub fn parse_str(input: &str) -> Result<Money, Error> {
+    pub const fn parse_str(input: &str) -> Result<Money, Error> {
         parse_en_us_utf8(input)
     }
-
-    /// Construct a Money instance from an i64
-    /// # Examples
-    /// ```
-    /// use postgres_money::Money;
-    /// let cents = 324023040222;
-    /// let money = Money::from(cents);
-    ///
-    /// assert_eq!("$3240230402.22", money.to_string());
-    /// ```
-    pub fn from(cents: i64) -> Money {
-        Money(cents)
-    }
 }
 
-fn parse_en_us_utf8(input: &str) -> Result<Money, Error> {
-    Amount::from(input)?.to_money()
-}
-
-#[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd, Debug)]
-enum AmountKind {
-    Negative,
-    Positive,
-}
-
-#[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd, Debug)]
-struct Amount {
-    kind: AmountKind,
-    dollars: String,
-    cents: String,
+/// Money from a string literal, parsed at compile time as
+/// [Money::parse_str] parses at run time.
+///
+/// An invalid or out of range literal fails to compile.
+///
+/// # Examples
+/// ```
+/// use postgres_money::{money, Money};
+///
+/// const FEE: Money = money!("$1,234.56");
+/// assert_eq!(FEE, Money::from_cents(123456));
+/// assert_eq!(money!("(0.50)"), Money::from_cents(-50));
+/// ```
+///
+/// ```compile_fail
+/// # use postgres_money::{money, Money};
+/// const FEE: Money = money!("$1.2.3");
+/// ```
+///
+/// ```compile_fail
+/// # use postgres_money::{money, Money};
+/// const FEE: Money = money!("92233720368547758.08");
+/// ```
+#[macro_export]
+macro_rules! money {
+    ($literal:literal) => {{
+        const MONEY: $crate::Money = match $crate::Money::parse_str($literal) {
+            ::core::result::Result::Ok(money) => money,
+            ::core::result::Result::Err($crate::Error::OutOfRange) => {
+                ::core::panic!(::core::concat!("money literal out of range: ", $literal))
+            }
+            ::core::result::Result::Err(_) => {
+                ::core::panic!(::core::concat!("invalid money literal: ", $literal))
+            }
+        };
+        MONEY
+    }};
 }
 
-impl Amount {
-    fn new(kind: AmountKind, inner: &str) -> Result<Self, Error> {
-        let caps = Self::valid_inner()
-            .captures(inner)
-            .ok_or(Error::InvalidString)?;
-
-        if caps.len() != 3 {
-            return Err(Error::InvalidString);
-        }
+/// Parse `[ws] [(] [sign] [$] [sign] dollars [. cents] [-] [$] [CR|DR] [)] [ws]`,
+/// with whitespace allowed between the parts as Postgres allows, and at most
+/// one of the negative markers that [MoneyFormat](crate::MoneyFormat) writes
+const fn parse_en_us_utf8(input: &str) -> Result<Money, Error> {
+    let s = input.as_bytes();
 
-        Ok(Amount {
-            kind,
-            dollars: Self::mk_string(caps.get(1)).replace(',', ""),
-            cents: Self::mk_string(caps.get(2)),
-        })
+    let mut i = skip_whitespace(s, 0);
+    let open = i < s.len() && s[i] == b'(';
+    if open {
+        i = skip_whitespace(s, i + 1);
     }
-
-    fn positive(s: &str) -> Result<Amount, Error> {
-        Self::new(AmountKind::Positive, s)
+    let sign = sign_at(s, i);
+    if sign != 0 {
+        i = skip_whitespace(s, i + 1);
     }
-
-    fn negative(s: &str) -> Result<Amount, Error> {
-        Self::new(AmountKind::Negative, s)
+    if i < s.len() && s[i] == b'$' {
+        i = skip_whitespace(s, i + 1);
     }
-
-    fn valid_inner() -> Regex {
-        Regex::new(r"^\$?(?P<dollars>[\d,]*)\.?(?P<cents>\d*$)").unwrap()
+    let inner_sign = sign_at(s, i);
+    if inner_sign != 0 {
+        i = skip_whitespace(s, i + 1);
     }
 
-    fn mk_string(m: Option<Match>) -> String {
-        m.map_or("", |m| m.as_str()).to_string()
+    let dollars_start = i;
+    while i < s.len() && (s[i].is_ascii_digit() || s[i] == b',') {
+        i += 1;
     }
-
-    fn from(s: &str) -> Result<Self, Error> {
-        let has_minus = Regex::new(r"-(.*)").unwrap();
-        let has_paren = Regex::new(r"\((.*)\)").unwrap();
-
-        let m: Vec<Regex> = vec![has_minus, has_paren]
-            .into_iter()
-            .filter(|r| r.is_match(s))
-            .collect();
-
-        return match m.len() {
-            0 => Self::positive(s),
-            1 => {
-                let transformed = m
-                    .into_iter()
-                    .fold(s, |s, r| r.captures(s).unwrap().get(1).unwrap().as_str());
-                Self::negative(transformed)
-            }
-            _ => Err(Error::InvalidString),
-        };
+    let dollars_end = i;
+    if i < s.len() && s[i] == b'.' {
+        i += 1;
     }
-
-    fn to_money(&self) -> Result<Money, Error> {
-        let inner = self.combine_dollars_and_cents()?;
-        Ok(Money(inner))
+    let cents_start = i;
+    while i < s.len() && s[i].is_ascii_digit() {
+        i += 1;
     }
+    let cents_end = i;
 
-    fn apply_sign(&self) -> i64 {
-        if self.kind == AmountKind::Negative {
-            -1
-        } else {
-            1
-        }
+    i = skip_whitespace(s, i);
+    let trailing_minus = i < s.len() && s[i] == b'-';
+    if trailing_minus {
+        i = skip_whitespace(s, i + 1);
     }
+    if i < s.len() && s[i] == b'$' {
+        i = skip_whitespace(s, i + 1);
+    }
+    let (credit, debit) = match suffix_at(s, i) {
+        Some(credit) => {
+            i = skip_whitespace(s, i + 2);
+            (credit, !credit)
+        }
+        None => (false, false),
+    };
+    let close = i < s.len() && s[i] == b')';
+    if close {
+        i = skip_whitespace(s, i + 1);
+    }
+    if i != s.len() || open != close {
+        return Err(Error::InvalidString);
+    }
+
+    let sign = match (sign, inner_sign) {
+        (sign, 0) | (0, sign) => sign,
+        _ => return Err(Error::InvalidString),
+    };
+    let markers = open as u8 + (sign == b'-') as u8 + trailing_minus as u8 + credit as u8;
+    let negative = match markers {
+        0 => false,
+        1 if !debit && sign != b'+' => true,
+        _ => return Err(Error::InvalidString),
+    };
+
+    let dollars = match parse_dollars(s, dollars_start, dollars_end) {
+        Ok(dollars) => dollars,
+        Err(e) => return Err(e),
+    };
+    let cents = rounded_cents(s, cents_start, cents_end);
+    let (dollars, cents) = if negative {
+        (-dollars, -cents)
+    } else {
+        (dollars, cents)
+    };
 
-    fn combine_dollars_and_cents(&self) -> Result<i64, Error> {
-        let dollars = mk_int(&self.dollars)? * self.apply_sign();
-        let cents = mk_rounded_cents(&self.cents)? * self.apply_sign();
-
-        dollars
-            .checked_mul(100)
-            .ok_or(Error::OutOfRange)?
-            .checked_add(cents)
-            .ok_or(Error::OutOfRange)
+    match dollars.checked_mul(100) {
+        Some(whole) => match whole.checked_add(cents) {
+            Some(inner) => Ok(Money(inner)),
+            None => Err(Error::OutOfRange),
+        },
+        None => Err(Error::OutOfRange),
     }
 }
 
-fn mk_rounded_cents(s: &str) -> Result<i64, Error> {
-    if s.len() > 2 {
-        round_cents(s)
-    } else {
-        mk_int(s)
+/// Skip whitespace as C `isspace` sees it, which unlike
+/// `is_ascii_whitespace` includes vertical tab
+const fn skip_whitespace(s: &[u8], mut i: usize) -> usize {
+    while i < s.len() && (s[i].is_ascii_whitespace() || s[i] == b'\x0b') {
+        i += 1;
     }
+    i
 }
 
-fn round_cents(s: &str) -> Result<i64, Error> {
-    let s = &s[..3];
-    let (s1, s2) = s.split_at(s.len() - 1);
-    let (i1, i2) = (mk_int(s1)?, mk_int(s2)?);
-    if i2 >= 5 {
-        Ok(i1 + 1)
+/// The `-` or `+` at `i`, or zero
+const fn sign_at(s: &[u8], i: usize) -> u8 {
+    if i < s.len() && (s[i] == b'-' || s[i] == b'+') {
+        s[i]
     } else {
-        Ok(i1)
+        0
     }
 }
 
-fn mk_int(s: &str) -> Result<i64, Error> {
-    if s.is_empty() {
-        return Ok(0);
+/// Whether a `CR` (true) or `DR` (false) suffix starts at `i`, in any case
+const fn suffix_at(s: &[u8], i: usize) -> Option<bool> {
+    if i + 1 >= s.len() || !s[i + 1].eq_ignore_ascii_case(&b'r') {
+        return None;
+    }
+    match s[i].to_ascii_uppercase() {
+        b'C' => Some(true),
+        b'D' => Some(false),
+        _ => None,
     }
+}
 
-    str::parse::<i64>(s).map_err(|e| {
-        // This is a janky workaround until ParseIntError.kind() is stable
-        match e.to_string().find("too large") {
-            Some(_) => Error::OutOfRange,
-            None => Error::ParseInt,
+/// The digits of `s[start..end]`, skipping grouping commas
+const fn parse_dollars(s: &[u8], start: usize, end: usize) -> Result<i64, Error> {
+    let mut dollars: i64 = 0;
+    let mut i = start;
+    while i < end {
+        if s[i] != b',' {
+            dollars = match dollars.checked_mul(10) {
+                Some(shifted) => match shifted.checked_add((s[i] - b'0') as i64) {
+                    Some(dollars) => dollars,
+                    None => return Err(Error::OutOfRange),
+                },
+                None => return Err(Error::OutOfRange),
+            };
         }
-    })
+        i += 1;
+    }
+    Ok(dollars)
+}
+
+/// The digits after the point as cents, rounding half up on the third
+const fn rounded_cents(s: &[u8], start: usize, end: usize) -> i64 {
+    // A single digit is tenths, as in `.5`
+    digit_at(s, start, end) * 10
+        + digit_at(s, start + 1, end)
+        + (digit_at(s, start + 2, end) >= 5) as i64
+}
+
+/// The digit at `i`, or zero past `end`
+const fn digit_at(s: &[u8], i: usize, end: usize) -> i64 {
+    if i < end {
+        (s[i] - b'0') as i64
+    } else {
+        0
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     // Money::parse_str
@@ -361,6 +425,43 @@ mod tests {
         assert_eq!(Money::parse_str("($123,456.78)"), Ok(Money(-12345678)))
     }
 
+    #[test]
+    fn test_valid_minus_after_symbol() {
+        assert_eq!(Money::parse_str("$-1.50"), Ok(Money(-150)))
+    }
+
+    #[test]
+    fn test_valid_trailing_minus() {
+        assert_eq!(Money::parse_str("$1,234.50-"), Ok(Money(-123450)))
+    }
+
+    #[test]
+    fn test_valid_credit_debit() {
+        assert_eq!(Money::parse_str("$1.50 CR"), Ok(Money(-150)));
+        assert_eq!(Money::parse_str("1.50cr"), Ok(Money(-150)));
+        assert_eq!(Money::parse_str("$1.50 DR"), Ok(Money(150)));
+        assert_eq!(Money::parse_str("($1.50 DR)"), Err(Error::InvalidString));
+    }
+
+    #[test]
+    fn test_invalid_sign_markers() {
+        for input in [
+            "-$1.50-",
+            "-(1.50)",
+            "(1.50",
+            "1.50)",
+            "1-50",
+            "$1.50- CR",
+            "--1",
+        ] {
+            assert_eq!(
+                Money::parse_str(input),
+                Err(Error::InvalidString),
+                "{input}"
+            );
+        }
+    }
+
     #[test]
     fn test_valid_min() {
         assert_eq!(Money::parse_str("-92233720368547758.08"), Ok(Money::min()))
@@ -422,4 +523,120 @@ mod tests {
     fn test_valid_neg_123456_78_int() {
         assert_eq!(Money::from(-12345678), Money(-12345678))
     }
+
+    #[test]
+    fn test_money_macro() {
+        const MIN: Money = crate::money!("-$92,233,720,368,547,758.08");
+        const ROUNDED: Money = crate::money!(" ($123.455) ");
+
+        assert_eq!(MIN, Money::min());
+        assert_eq!(ROUNDED, Money(-12346));
+        assert_eq!(crate::money!("5 CR"), Money(-500));
+    }
+
+    #[test]
+    fn test_valid_tenths() {
+        assert_eq!(Money::parse_str(".5"), Ok(Money(50)));
+        assert_eq!(Money::parse_str("-$1,234.5"), Ok(Money(-123450)));
+    }
+
+    #[test]
+    fn test_invalid_non_ascii_digits() {
+        // Found by fuzzing, when the third decimal was sliced mid-character
+        assert_eq!(Money::parse_str("$.50\u{0668}"), Err(Error::InvalidString));
+        assert_eq!(Money::parse_str("\u{0661}"), Err(Error::InvalidString));
+    }
+
+    #[test]
+    fn test_valid_whitespace_and_plus() {
+        assert_eq!(Money::parse_str(" $ 5 "), Ok(Money(500)));
+        assert_eq!(Money::parse_str("( 5 )"), Ok(Money(-500)));
+        assert_eq!(Money::parse_str("+$5"), Ok(Money(500)));
+        assert_eq!(Money::parse_str("$+5"), Ok(Money(500)));
+        assert_eq!(Money::parse_str("\x0b5\x0b"), Ok(Money(500)));
+        assert_eq!(Money::parse_str("+5-"), Err(Error::InvalidString));
+        assert_eq!(Money::parse_str("-$+5"), Err(Error::InvalidString));
+    }
+
+    // Differential tests against Postgres, replaying testdata/capture.sql
+
+    /// Inputs where the parser deliberately differs from Postgres: it accepts
+    /// accounting suffixes, and rejects repeated currency symbols and
+    /// unbalanced parentheses
+    const KNOWN_DIVERGENCES: [&str; 6] = ["5CR", "5 CR", "5 DR", "$$1", "(1.50", "1.50)"];
+
+    /// Lines of a `\copy` TSV, with the `\v` escape for vertical tab decoded
+    fn corpus(tsv: &str) -> impl Iterator<Item = (String, &str)> {
+        tsv.lines().map(|line| {
+            let (input, output) = line.split_once('\t').unwrap();
+            (input.replace("\\v", "\x0b"), output)
+        })
+    }
+
+    #[test]
+    fn test_postgres_cash_in_corpus() {
+        for (input, output) in corpus(include_str!("../testdata/cash_in.tsv")) {
+            if KNOWN_DIVERGENCES.contains(&input.as_str()) {
+                continue;
+            }
+
+            let expected = match output {
+                "!invalid" => Err(Error::InvalidString),
+                "!out_of_range" => Err(Error::OutOfRange),
+                output => Ok(Money::parse_str(output).unwrap()),
+            };
+            assert_eq!(Money::parse_str(&input), expected, "input {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_postgres_cash_out_corpus() {
+        let format = crate::MoneyFormat::new().grouping(true);
+        for (cents, output) in corpus(include_str!("../testdata/cash_out.tsv")) {
+            let money = Money(cents.parse().unwrap());
+            assert_eq!(format.format(money), output);
+            assert_eq!(Money::parse_str(output), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_known_divergences_are_in_corpus() {
+        let inputs: Vec<_> = corpus(include_str!("../testdata/cash_in.tsv"))
+            .map(|(input, _)| input)
+            .collect();
+        for input in KNOWN_DIVERGENCES {
+            assert!(inputs.iter().any(|i| i == input), "{input:?}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_never_panics(input in "\\PC*") {
+            let _ = Money::parse_str(&input);
+        }
+
+        #[test]
+        fn prop_grouped_round_trip(inner in any::<i64>()) {
+            let money = Money(inner);
+            let grouped = crate::MoneyFormat::new().grouping(true).format(money);
+            prop_assert_eq!(Money::parse_str(&grouped), Ok(money));
+        }
+
+        #[test]
+        fn prop_surrounding_whitespace(inner in any::<i64>(), before in " {0,3}", after in " {0,3}") {
+            let money = Money(inner);
+            prop_assert_eq!(Money::parse_str(&format!("{before}{money}{after}")), Ok(money));
+        }
+
+        #[test]
+        fn prop_rounds_extra_digits_half_up(
+            dollars in 0i64..1_000_000,
+            tenths_of_cents in 0i64..1000,
+            rest in "[0-9]{0,5}",
+        ) {
+            let input = format!("{dollars}.{tenths_of_cents:03}{rest}");
+            let expected = dollars * 100 + (tenths_of_cents + 5) / 10;
+            prop_assert_eq!(Money::parse_str(&input), Ok(Money(expected)));
+        }
+    }
 }
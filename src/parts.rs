@@ -0,0 +1,128 @@
+use crate::{Error, Money};
+
+/// Money split into its sign, whole dollars and cents.
+///
+/// Unlike the inner `i64`, the magnitude of every Money fits, including
+/// [Money::min].
+///
+/// # Examples
+/// ```
+/// use postgres_money::{Money, MoneyParts};
+///
+/// let parts = Money::from(-123456).parts();
+/// assert_eq!(
+///     parts,
+///     MoneyParts { negative: true, major: 1234, minor: 56 }
+/// );
+/// assert_eq!(Money::from_parts(parts), Ok(Money::from(-123456)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MoneyParts {
+    /// Whether the amount is below zero
+    pub negative: bool,
+    /// The whole dollars of the magnitude
+    pub major: u64,
+    /// The cents of the magnitude, from 0 to 99
+    pub minor: u8,
+}
+
+impl Money {
+    /// Split into sign, dollars and cents
+    pub const fn parts(self) -> MoneyParts {
+        let magnitude = self.0.unsigned_abs();
+        MoneyParts {
+            negative: self.0 < 0,
+            major: magnitude / 100,
+            minor: (magnitude % 100) as u8,
+        }
+    }
+
+    /// Reassemble Money from [Money::parts].
+    ///
+    /// Returns `Error::InvalidArgument` if `minor` is over 99, and
+    /// `Error::OutOfRange` if the amount does not fit. A negative zero is
+    /// zero.
+    pub const fn from_parts(parts: MoneyParts) -> Result<Money, Error> {
+        if parts.minor >= 100 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let magnitude = match parts.major.checked_mul(100) {
+            Some(whole) => whole + parts.minor as u64,
+            None => return Err(Error::OutOfRange),
+        };
+
+        if parts.negative {
+            match 0i64.checked_sub_unsigned(magnitude) {
+                Some(inner) => Ok(Money(inner)),
+                None => Err(Error::OutOfRange),
+            }
+        } else {
+            match 0i64.checked_add_unsigned(magnitude) {
+                Some(inner) => Ok(Money(inner)),
+                None => Err(Error::OutOfRange),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(negative: bool, major: u64, minor: u8) -> MoneyParts {
+        MoneyParts {
+            negative,
+            major,
+            minor,
+        }
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(Money(0).parts(), parts(false, 0, 0));
+        assert_eq!(Money(-5).parts(), parts(true, 0, 5));
+        assert_eq!(Money(123456).parts(), parts(false, 1234, 56));
+        assert_eq!(Money::max().parts(), parts(false, 92233720368547758, 7));
+        assert_eq!(Money::min().parts(), parts(true, 92233720368547758, 8));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for money in [
+            Money::min(),
+            Money(-100),
+            Money(-1),
+            Money(0),
+            Money(99),
+            Money::max(),
+        ] {
+            assert_eq!(Money::from_parts(money.parts()), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_from_parts_negative_zero() {
+        assert_eq!(Money::from_parts(parts(true, 0, 0)), Ok(Money(0)));
+    }
+
+    #[test]
+    fn test_from_parts_invalid() {
+        assert_eq!(
+            Money::from_parts(parts(false, 1, 100)),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Money::from_parts(parts(false, 92233720368547758, 8)),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            Money::from_parts(parts(true, 92233720368547758, 9)),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            Money::from_parts(parts(true, u64::MAX, 0)),
+            Err(Error::OutOfRange)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Postgres extension support.
+//!
+//! `money` is an int8 underneath and, like int8, is passed by value in a
+//! Datum on every platform pgrx supports. The extension crate selects the
+//! Postgres version through pgrx's own `pg13`..`pg18` features.
+//!
+//! The tests below need one of those features too, along with the server
+//! headers for that version (`postgresql-server-dev-15` on Debian):
+//!
+//! ```text
+//! PGRX_PG_CONFIG_PATH=/usr/lib/postgresql/15/bin/pg_config \
+//!     cargo test --features pgrx,pgrx/pg15 --lib pgrx_impl
+//! ```
+
+use pgrx::callconv::{Arg, ArgAbi, BoxRet, FcInfo};
+use pgrx::datum::Datum;
+use pgrx::nullable::Nullable;
+use pgrx::{pg_sys, FromDatum, IntoDatum};
+
+use crate::Money;
+
+// Named CASHOID before Postgres 14
+const MONEY_OID: pg_sys::Oid = pg_sys::Oid::from_u32(790);
+
+impl FromDatum for Money {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        typoid: pg_sys::Oid,
+    ) -> Option<Money> {
+        i64::from_polymorphic_datum(datum, is_null, typoid).map(Money)
+    }
+}
+
+impl IntoDatum for Money {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.inner().into_datum()
+    }
+
+    fn type_oid() -> pg_sys::Oid {
+        MONEY_OID
+    }
+}
+
+unsafe impl<'fcx> ArgAbi<'fcx> for Money {
+    unsafe fn unbox_arg_unchecked(arg: Arg<'_, 'fcx>) -> Money {
+        let index = arg.index();
+        arg.unbox_arg_using_from_datum()
+            .unwrap_or_else(|| panic!("argument {index} must not be null"))
+    }
+
+    unsafe fn unbox_nullable_arg(arg: Arg<'_, 'fcx>) -> Nullable<Money> {
+        arg.unbox_arg_using_from_datum().into()
+    }
+}
+
+unsafe impl BoxRet for Money {
+    unsafe fn box_into<'fcx>(self, fcinfo: &mut FcInfo<'fcx>) -> Datum<'fcx> {
+        fcinfo.return_raw_datum(pg_sys::Datum::from(self.inner()))
+    }
+}
+
+pgrx::impl_sql_translatable!(Money, "money");
+
+#[cfg(test)]
+mod tests {
+    use pgrx::callconv::{BoxRet, FcInfo};
+    use pgrx::pg_sys;
+    use pgrx::{FromDatum, IntoDatum};
+
+    use crate::Money;
+
+    /// A FunctionCallInfo with room for `N` arguments, laid out as Postgres'
+    /// `LOCAL_FCINFO` lays it out on the stack
+    #[repr(C)]
+    struct LocalFcInfo<const N: usize> {
+        base: pg_sys::FunctionCallInfoBaseData,
+        args: [pg_sys::NullableDatum; N],
+    }
+
+    impl<const N: usize> LocalFcInfo<N> {
+        fn new(args: [Option<pg_sys::Datum>; N]) -> LocalFcInfo<N> {
+            LocalFcInfo {
+                base: pg_sys::FunctionCallInfoBaseData {
+                    nargs: N as i16,
+                    ..Default::default()
+                },
+                args: args.map(|arg| pg_sys::NullableDatum {
+                    value: arg.unwrap_or_default(),
+                    isnull: arg.is_none(),
+                }),
+            }
+        }
+    }
+
+    /// Call a `money -> money` function the way a `#[pg_extern]` wrapper
+    /// does, returning the result datum and its null flag
+    fn call_money_fn(
+        arg: Option<pg_sys::Datum>,
+        f: impl FnOnce(Option<Money>) -> Option<Money>,
+    ) -> (pg_sys::Datum, bool) {
+        let mut local = LocalFcInfo::new([arg]);
+        let ptr: *mut LocalFcInfo<1> = &mut local;
+        let mut fcinfo = unsafe { FcInfo::from_ptr(ptr.cast()) };
+        let arg = unsafe { fcinfo.args().next_arg::<Money>() }.unwrap();
+        let datum = match f(arg.into_option()) {
+            Some(money) => unsafe { money.box_into(&mut fcinfo) },
+            None => fcinfo.return_null(),
+        };
+        (datum.sans_lifetime(), local.base.isnull)
+    }
+
+    fn round_trip(money: Money) -> Option<Money> {
+        unsafe { Money::from_datum(money.into_datum().unwrap(), false) }
+    }
+
+    #[test]
+    fn test_datum_round_trip() {
+        for money in [
+            Money::min(),
+            Money(-1),
+            Money::none(),
+            Money(1),
+            Money::max(),
+        ] {
+            assert_eq!(round_trip(money), Some(money));
+        }
+    }
+
+    #[test]
+    fn test_datum_is_int8() {
+        let datum = Money::min().into_datum().unwrap();
+        assert_eq!(unsafe { i64::from_datum(datum, false) }, Some(i64::MIN));
+        assert_eq!(Money::type_oid(), pg_sys::Oid::from_u32(790));
+    }
+
+    #[test]
+    fn test_null_datum() {
+        assert_eq!(
+            unsafe { Money::from_datum(pg_sys::Datum::default(), true) },
+            None
+        );
+    }
+
+    #[test]
+    fn test_function_round_trip() {
+        for money in [Money::min(), Money::none(), Money::max()] {
+            let (datum, is_null) = call_money_fn(money.into_datum(), |arg| arg);
+            assert!(!is_null);
+            assert_eq!(unsafe { Money::from_datum(datum, false) }, Some(money));
+        }
+    }
+
+    #[test]
+    fn test_function_null_argument() {
+        let (_, is_null) = call_money_fn(None, |arg| {
+            assert_eq!(arg, None);
+            arg
+        });
+        assert!(is_null);
+    }
+
+    #[test]
+    fn test_sql_translation() {
+        use pgrx::pgrx_sql_entity_graph::metadata::{ReturnsRef, SqlMappingRef, SqlTranslatable};
+
+        assert_eq!(
+            <Money as SqlTranslatable>::ARGUMENT_SQL,
+            Ok(SqlMappingRef::literal("money"))
+        );
+        assert_eq!(
+            <Money as SqlTranslatable>::RETURN_SQL,
+            Ok(ReturnsRef::One(SqlMappingRef::literal("money")))
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Discounts and markups on [`Money`].
+//!
+//! A [`PriceRule`] describes a single adjustment, either a proportion of the
+//! price or a fixed amount, together with the rounding mode and any limits on
+//! the adjustment or the resulting price. Proportions use exact [`Rate`]
+//! arithmetic, so the adjustment is rounded exactly once.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::pricing::PriceRule;
+//! use postgres_money::{BasisPoints, Money, Rounding};
+//!
+//! // 12.5% off, but never more than $5 off
+//! let rule = PriceRule::new(BasisPoints(1250))
+//!     .rounding(Rounding::Floor)
+//!     .max_amount(Money::from(500));
+//!
+//! assert_eq!(Money::from(1999).apply_discount(&rule), Ok(Money::from(1750)));
+//! assert_eq!(Money::from(9999).apply_discount(&rule), Ok(Money::from(9499)));
+//! ```
+
+use crate::{BasisPoints, Error, Money, Percent, Rate, Rounding};
+
+/// How large an adjustment is
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Adjustment {
+    /// A proportion of the price
+    Rate(Rate),
+    /// A fixed amount regardless of the price
+    Amount(Money),
+}
+
+impl From<Rate> for Adjustment {
+    fn from(rate: Rate) -> Adjustment {
+        Adjustment::Rate(rate)
+    }
+}
+
+impl From<Percent> for Adjustment {
+    fn from(percent: Percent) -> Adjustment {
+        Adjustment::Rate(percent.into())
+    }
+}
+
+impl From<BasisPoints> for Adjustment {
+    fn from(bps: BasisPoints) -> Adjustment {
+        Adjustment::Rate(bps.into())
+    }
+}
+
+impl From<Money> for Adjustment {
+    fn from(amount: Money) -> Adjustment {
+        Adjustment::Amount(amount)
+    }
+}
+
+/// A discount or markup with optional limits.
+///
+/// The adjustment amount is computed first and clamped between
+/// [`min_amount`](PriceRule::min_amount) and
+/// [`max_amount`](PriceRule::max_amount). The adjusted price is then clamped
+/// between [`min_price`](PriceRule::min_price) and
+/// [`max_price`](PriceRule::max_price).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PriceRule {
+    adjustment: Adjustment,
+    rounding: Rounding,
+    min_amount: Option<Money>,
+    max_amount: Option<Money>,
+    min_price: Option<Money>,
+    max_price: Option<Money>,
+}
+
+impl PriceRule {
+    /// A rule applying `adjustment` without limits, rounding half away from zero
+    pub fn new(adjustment: impl Into<Adjustment>) -> PriceRule {
+        PriceRule {
+            adjustment: adjustment.into(),
+            rounding: Rounding::default(),
+            min_amount: None,
+            max_amount: None,
+            min_price: None,
+            max_price: None,
+        }
+    }
+
+    /// Round proportional adjustments with `rounding`
+    pub fn rounding(mut self, rounding: Rounding) -> PriceRule {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Adjust by at least `amount`
+    pub fn min_amount(mut self, amount: Money) -> PriceRule {
+        self.min_amount = Some(amount);
+        self
+    }
+
+    /// Adjust by at most `amount`
+    pub fn max_amount(mut self, amount: Money) -> PriceRule {
+        self.max_amount = Some(amount);
+        self
+    }
+
+    /// Never produce a price below `price`
+    pub fn min_price(mut self, price: Money) -> PriceRule {
+        self.min_price = Some(price);
+        self
+    }
+
+    /// Never produce a price above `price`
+    pub fn max_price(mut self, price: Money) -> PriceRule {
+        self.max_price = Some(price);
+        self
+    }
+
+    /// The size of the adjustment to `price`, after rounding and clamping to
+    /// the amount limits.
+    ///
+    /// Returns `Error::InvalidArgument` if the adjustment is negative or the
+    /// amount limits cross, and `Error::OutOfRange` on overflow.
+    pub fn amount(&self, price: Money) -> Result<Money, Error> {
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return Err(Error::InvalidArgument);
+            }
+        }
+
+        let amount = match self.adjustment {
+            Adjustment::Rate(rate) if rate.is_negative() => return Err(Error::InvalidArgument),
+            Adjustment::Amount(amount) if amount < Money::none() => {
+                return Err(Error::InvalidArgument)
+            }
+            Adjustment::Rate(rate) => price.mul_rate(rate, self.rounding)?,
+            Adjustment::Amount(amount) => amount,
+        };
+
+        Ok(clamp(amount, self.min_amount, self.max_amount))
+    }
+
+    fn clamp_price(&self, price: Money) -> Result<Money, Error> {
+        if let (Some(min), Some(max)) = (self.min_price, self.max_price) {
+            if min > max {
+                return Err(Error::InvalidArgument);
+            }
+        }
+        Ok(clamp(price, self.min_price, self.max_price))
+    }
+}
+
+fn clamp(value: Money, min: Option<Money>, max: Option<Money>) -> Money {
+    let value = max.map_or(value, |max| value.min(max));
+    min.map_or(value, |min| value.max(min))
+}
+
+impl Money {
+    /// Reduce this price according to `rule`.
+    ///
+    /// Returns `Error::InvalidArgument` if the rule is inconsistent, and
+    /// `Error::OutOfRange` on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::pricing::PriceRule;
+    /// use postgres_money::{Money, Percent};
+    ///
+    /// // $10 off, but the price cannot drop below zero
+    /// let rule = PriceRule::new(Money::from(1000)).min_price(Money::none());
+    ///
+    /// assert_eq!(Money::from(2500).apply_discount(&rule), Ok(Money::from(1500)));
+    /// assert_eq!(Money::from(500).apply_discount(&rule), Ok(Money::none()));
+    ///
+    /// let rule = PriceRule::new(Percent(15));
+    ///
+    /// assert_eq!(Money::from(1999).apply_discount(&rule), Ok(Money::from(1699)));
+    /// ```
+    pub fn apply_discount(self, rule: &PriceRule) -> Result<Money, Error> {
+        let discounted = self
+            .checked_sub(rule.amount(self)?)
+            .ok_or(Error::OutOfRange)?;
+        rule.clamp_price(discounted)
+    }
+
+    /// Increase this price according to `rule`.
+    ///
+    /// Returns `Error::InvalidArgument` if the rule is inconsistent, and
+    /// `Error::OutOfRange` on overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::pricing::PriceRule;
+    /// use postgres_money::{Money, Percent};
+    ///
+    /// // 40% markup of at least $1
+    /// let rule = PriceRule::new(Percent(40)).min_amount(Money::from(100));
+    ///
+    /// assert_eq!(Money::from(1000).apply_markup(&rule), Ok(Money::from(1400)));
+    /// assert_eq!(Money::from(150).apply_markup(&rule), Ok(Money::from(250)));
+    /// ```
+    pub fn apply_markup(self, rule: &PriceRule) -> Result<Money, Error> {
+        let marked_up = self
+            .checked_add(rule.amount(self)?)
+            .ok_or(Error::OutOfRange)?;
+        rule.clamp_price(marked_up)
+    }
+}
+
+/// A set of rules chosen by price, such as a markup that shrinks as the cost
+/// rises
+///
+/// # Examples
+/// ```
+/// use postgres_money::pricing::{PriceRule, Tiers};
+/// use postgres_money::{Money, Percent};
+///
+/// let tiers = Tiers::new(vec![
+///     (Money::none(), PriceRule::new(Percent(50))),
+///     (Money::from(10_000), PriceRule::new(Percent(30))),
+///     (Money::from(100_000), PriceRule::new(Percent(20))),
+/// ])
+/// .unwrap();
+///
+/// assert_eq!(tiers.apply_markup(Money::from(5000)), Ok(Money::from(7500)));
+/// assert_eq!(tiers.apply_markup(Money::from(10_000)), Ok(Money::from(13_000)));
+/// assert_eq!(tiers.apply_markup(Money::from(200_000)), Ok(Money::from(240_000)));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Tiers {
+    tiers: Vec<(Money, PriceRule)>,
+}
+
+impl Tiers {
+    /// Create tiers from `(threshold, rule)` pairs. Each rule applies to
+    /// prices at or above its threshold and below the next one.
+    ///
+    /// Returns `Error::InvalidArgument` unless thresholds strictly increase.
+    pub fn new(tiers: Vec<(Money, PriceRule)>) -> Result<Tiers, Error> {
+        if tiers.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(Error::InvalidArgument);
+        }
+        Ok(Tiers { tiers })
+    }
+
+    /// The rule for `price`, or `None` if it is below every threshold
+    pub fn rule_for(&self, price: Money) -> Option<&PriceRule> {
+        let index = self
+            .tiers
+            .partition_point(|(threshold, _)| *threshold <= price);
+        index.checked_sub(1).map(|i| &self.tiers[i].1)
+    }
+
+    /// Discount `price` by the matching rule, leaving it unchanged if none match
+    pub fn apply_discount(&self, price: Money) -> Result<Money, Error> {
+        self.rule_for(price)
+            .map_or(Ok(price), |rule| price.apply_discount(rule))
+    }
+
+    /// Mark up `price` by the matching rule, leaving it unchanged if none match
+    pub fn apply_markup(&self, price: Money) -> Result<Money, Error> {
+        self.rule_for(price)
+            .map_or(Ok(price), |rule| price.apply_markup(rule))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discount_rounding() {
+        // 15% of $0.99 is 14.85 cents
+        let rule = |rounding| PriceRule::new(Percent(15)).rounding(rounding);
+
+        assert_eq!(
+            Money(99).apply_discount(&rule(Rounding::HalfUp)),
+            Ok(Money(84))
+        );
+        assert_eq!(
+            Money(99).apply_discount(&rule(Rounding::Down)),
+            Ok(Money(85))
+        );
+        assert_eq!(Money(99).apply_discount(&rule(Rounding::Up)), Ok(Money(84)));
+    }
+
+    #[test]
+    fn test_markup_ties() {
+        // 10% of $10.05 is exactly 100.5 cents
+        let rule = |rounding| PriceRule::new(Percent(10)).rounding(rounding);
+
+        assert_eq!(
+            Money(1005).apply_markup(&rule(Rounding::HalfUp)),
+            Ok(Money(1106))
+        );
+        assert_eq!(
+            Money(1005).apply_markup(&rule(Rounding::HalfEven)),
+            Ok(Money(1105))
+        );
+    }
+
+    #[test]
+    fn test_amount_limits() {
+        let rule = PriceRule::new(Percent(10))
+            .min_amount(Money(50))
+            .max_amount(Money(200));
+
+        assert_eq!(rule.amount(Money(100)), Ok(Money(50)));
+        assert_eq!(rule.amount(Money(1000)), Ok(Money(100)));
+        assert_eq!(rule.amount(Money(10000)), Ok(Money(200)));
+    }
+
+    #[test]
+    fn test_price_limits() {
+        let rule = PriceRule::new(Percent(50))
+            .min_price(Money(400))
+            .max_price(Money(1200));
+
+        assert_eq!(Money(600).apply_discount(&rule), Ok(Money(400)));
+        assert_eq!(Money(1000).apply_markup(&rule), Ok(Money(1200)));
+    }
+
+    #[test]
+    fn test_fixed_discount_can_go_negative_without_floor() {
+        let rule = PriceRule::new(Money(1000));
+        assert_eq!(Money(500).apply_discount(&rule), Ok(Money(-500)));
+    }
+
+    #[test]
+    fn test_negative_adjustments_rejected() {
+        assert_eq!(
+            Money(100).apply_discount(&PriceRule::new(Percent(-10))),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Money(100).apply_markup(&PriceRule::new(Money(-10))),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_crossed_limits_rejected() {
+        let rule = PriceRule::new(Percent(10))
+            .min_amount(Money(10))
+            .max_amount(Money(5));
+        assert_eq!(
+            Money(100).apply_discount(&rule),
+            Err(Error::InvalidArgument)
+        );
+
+        let rule = PriceRule::new(Percent(10))
+            .min_price(Money(10))
+            .max_price(Money(5));
+        assert_eq!(Money(100).apply_markup(&rule), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let rule = PriceRule::new(Percent(1));
+        assert_eq!(Money::max().apply_markup(&rule), Err(Error::OutOfRange));
+        assert_eq!(
+            Money::min().apply_discount(&PriceRule::new(Money(1))),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_tiers() {
+        let tiers = Tiers::new(vec![
+            (Money(1000), PriceRule::new(Percent(10))),
+            (Money(5000), PriceRule::new(Money(1000))),
+        ])
+        .unwrap();
+
+        assert_eq!(tiers.apply_discount(Money(999)), Ok(Money(999)));
+        assert_eq!(tiers.apply_discount(Money(1000)), Ok(Money(900)));
+        assert_eq!(tiers.apply_discount(Money(4999)), Ok(Money(4499)));
+        assert_eq!(tiers.apply_discount(Money(5000)), Ok(Money(4000)));
+    }
+
+    #[test]
+    fn test_tiers_unsorted() {
+        assert_eq!(
+            Tiers::new(vec![
+                (Money(5000), PriceRule::new(Percent(10))),
+                (Money(1000), PriceRule::new(Percent(20))),
+            ]),
+            Err(Error::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Proptest strategies for Money.
+//!
+//! Each strategy weights its boundaries, so that properties are checked
+//! against [Money::min], [Money::max] and amounts one cent either side of
+//! zero far more often than a uniform choice would manage. `any::<Money>()`
+//! draws from [any_money].
+//!
+//! # Examples
+//! ```
+//! use postgres_money::proptest::non_negative;
+//! use postgres_money::Money;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&non_negative(), |money| {
+//!         assert!(money >= Money::from(0));
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use ::proptest::prelude::*;
+use ::proptest::sample;
+
+use crate::Money;
+
+/// Any Money: mostly uniform, with boundaries and small amounts mixed in
+pub fn any_money() -> impl Strategy<Value = Money> {
+    prop_oneof![
+        1 => sample::select(&Money::BOUNDARIES[..]),
+        2 => (-100_000i64..=100_000).prop_map(Money),
+        7 => any::<i64>().prop_map(Money),
+    ]
+}
+
+/// Money from `min` to `max` inclusive, with both ends weighted.
+///
+/// # Panics
+/// If `min` is greater than `max`.
+pub fn between(min: Money, max: Money) -> impl Strategy<Value = Money> {
+    assert!(min <= max, "empty range {min}..={max}");
+    prop_oneof![
+        1 => Just(min),
+        1 => Just(max),
+        8 => (min.inner()..=max.inner()).prop_map(Money),
+    ]
+}
+
+/// Money of zero or more, with zero and one cent weighted
+pub fn non_negative() -> impl Strategy<Value = Money> {
+    prop_oneof![
+        1 => Just(Money(1)),
+        9 => between(Money(0), Money::max()),
+    ]
+}
+
+/// Money of one cent or more
+pub fn positive() -> impl Strategy<Value = Money> {
+    prop_oneof![
+        1 => Just(Money(2)),
+        9 => between(Money(1), Money::max()),
+    ]
+}
+
+/// Amounts shaped like prices and payments: spread evenly over orders of
+/// magnitude from a cent to a billion dollars, often whole dollars or ending
+/// in 99 cents, and occasionally negative like a refund
+pub fn realistic() -> impl Strategy<Value = Money> {
+    let magnitude = (1u32..=11).prop_flat_map(|digits| 10i64.pow(digits - 1)..10i64.pow(digits));
+    (magnitude, 0u8..10, 0u8..10).prop_map(|(cents, ending, sign)| {
+        // Amounts under a dollar have no whole dollars to round to
+        let cents = match ending {
+            0..=2 if cents >= 100 => cents / 100 * 100,
+            3 if cents >= 100 => cents / 100 * 100 + 99,
+            _ => cents,
+        };
+        Money(if sign == 0 { -cents } else { cents })
+    })
+}
+
+impl Arbitrary for Money {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Money>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        any_money().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::strategy::ValueTree;
+    use ::proptest::test_runner::TestRunner;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn prop_between(money in between(Money(-500), Money(250))) {
+            prop_assert!((Money(-500)..=Money(250)).contains(&money));
+        }
+
+        #[test]
+        fn prop_non_negative(money in non_negative()) {
+            prop_assert!(money >= Money(0));
+        }
+
+        #[test]
+        fn prop_positive(money in positive()) {
+            prop_assert!(money > Money(0));
+        }
+
+        #[test]
+        fn prop_realistic(money in realistic()) {
+            prop_assert!(money.inner().unsigned_abs() < 100_000_000_000);
+        }
+
+        #[test]
+        fn prop_any(money in any::<Money>()) {
+            prop_assert_eq!(Money::parse_str(&money.to_string()), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_between_single_value() {
+        let mut runner = TestRunner::default();
+        let tree = between(Money(7), Money(7)).new_tree(&mut runner).unwrap();
+        assert_eq!(tree.current(), Money(7));
+    }
+
+    #[test]
+    fn test_realistic_never_zero() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = realistic();
+        for _ in 0..10_000 {
+            let tree = strategy.new_tree(&mut runner).unwrap();
+            assert_ne!(tree.current(), Money(0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn test_between_empty() {
+        let _ = between(Money(1), Money(0));
+    }
+}
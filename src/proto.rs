@@ -0,0 +1,187 @@
+//! Conversions to and from the protobuf `google.type.Money` message.
+//!
+//! [GoogleMoney] is wire compatible with `google.type.Money`. Code generated
+//! by `prost-build` can use it in place of its own copy of the message with
+//! `config.extern_path(".google.type.Money", "::postgres_money::proto::GoogleMoney")`.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::proto::GoogleMoney;
+//! use postgres_money::{Money, Rounding};
+//!
+//! let message = GoogleMoney::from_money(Money::from(-1250), "USD");
+//! assert_eq!((message.units, message.nanos), (-12, -500_000_000));
+//!
+//! let money = message.to_money("USD", Rounding::HalfUp).unwrap();
+//! assert_eq!(money, Money::from(-1250));
+//! ```
+
+use crate::{Error, Money, Rounding};
+
+const NANOS_PER_CENT: i32 = 10_000_000;
+const NANOS_PER_UNIT: i32 = 1_000_000_000;
+
+/// The `google.type.Money` message: an amount in a currency, as whole units
+/// and nanos (10^-9) of a unit
+#[derive(Clone, Eq, Hash, PartialEq, prost::Message)]
+pub struct GoogleMoney {
+    /// The three-letter ISO 4217 currency code
+    #[prost(string, tag = "1")]
+    pub currency_code: String,
+    /// The whole units of the amount
+    #[prost(int64, tag = "2")]
+    pub units: i64,
+    /// Nanos of a unit, between -999,999,999 and 999,999,999 and with the
+    /// same sign as `units` when that is nonzero
+    #[prost(int32, tag = "3")]
+    pub nanos: i32,
+}
+
+impl GoogleMoney {
+    /// The exact message for `money` in `currency_code`
+    pub fn from_money(money: Money, currency_code: impl Into<String>) -> GoogleMoney {
+        let cents = money.inner();
+        GoogleMoney {
+            currency_code: currency_code.into(),
+            units: cents / 100,
+            // The remainder keeps the sign of the dividend, as nanos must
+            nanos: (cents % 100) as i32 * NANOS_PER_CENT,
+        }
+    }
+
+    /// Convert to Money, rounding any fraction of a cent according to
+    /// `rounding`.
+    ///
+    /// Returns `Error::CurrencyMismatch` unless the message is in
+    /// `currency_code`, `Error::InvalidArgument` if `nanos` is out of its
+    /// range or has the wrong sign, and `Error::OutOfRange` if the amount does
+    /// not fit in Money.
+    pub fn to_money(&self, currency_code: &str, rounding: Rounding) -> Result<Money, Error> {
+        if self.currency_code != currency_code {
+            return Err(Error::CurrencyMismatch);
+        }
+
+        let opposite_signs =
+            (self.units > 0 && self.nanos < 0) || (self.units < 0 && self.nanos > 0);
+        if self.nanos.unsigned_abs() >= NANOS_PER_UNIT.unsigned_abs() || opposite_signs {
+            return Err(Error::InvalidArgument);
+        }
+
+        let nanos = i128::from(self.units) * i128::from(NANOS_PER_UNIT) + i128::from(self.nanos);
+        Money::from_i128(rounding.div(nanos, i128::from(NANOS_PER_CENT)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use super::*;
+
+    fn message(units: i64, nanos: i32) -> GoogleMoney {
+        GoogleMoney {
+            currency_code: "USD".to_owned(),
+            units,
+            nanos,
+        }
+    }
+
+    #[test]
+    fn test_from_money() {
+        assert_eq!(
+            GoogleMoney::from_money(Money(123456), "USD"),
+            message(1234, 560_000_000)
+        );
+        assert_eq!(
+            GoogleMoney::from_money(Money(-5), "USD"),
+            message(0, -50_000_000)
+        );
+        assert_eq!(
+            GoogleMoney::from_money(Money::min(), "USD"),
+            message(-92233720368547758, -80_000_000)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for money in [
+            Money::min(),
+            Money(-1),
+            Money(0),
+            Money(123456),
+            Money::max(),
+        ] {
+            let message = GoogleMoney::from_money(money, "USD");
+            assert_eq!(message.to_money("USD", Rounding::HalfEven), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_to_money_rounds_nanos() {
+        let message = message(1, 5_000_000);
+        assert_eq!(message.to_money("USD", Rounding::HalfUp), Ok(Money(101)));
+        assert_eq!(message.to_money("USD", Rounding::HalfEven), Ok(Money(100)));
+        assert_eq!(message.to_money("USD", Rounding::Floor), Ok(Money(100)));
+
+        let message = self::message(-1, -1);
+        assert_eq!(message.to_money("USD", Rounding::Floor), Ok(Money(-101)));
+        assert_eq!(message.to_money("USD", Rounding::Down), Ok(Money(-100)));
+    }
+
+    #[test]
+    fn test_to_money_out_of_range() {
+        let max = GoogleMoney::from_money(Money::max(), "USD");
+        assert_eq!(
+            message(max.units, max.nanos + NANOS_PER_CENT).to_money("USD", Rounding::HalfUp),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            message(i64::MIN, 0).to_money("USD", Rounding::HalfUp),
+            Err(Error::OutOfRange)
+        );
+        // Rounds down into range
+        assert_eq!(
+            message(max.units, max.nanos + 1).to_money("USD", Rounding::HalfUp),
+            Ok(Money::max())
+        );
+    }
+
+    #[test]
+    fn test_to_money_invalid_nanos() {
+        for (units, nanos) in [
+            (1, -1),
+            (-1, 1),
+            (0, 1_000_000_000),
+            (0, -1_000_000_000),
+            (0, i32::MIN),
+            (0, i32::MAX),
+        ] {
+            assert_eq!(
+                message(units, nanos).to_money("USD", Rounding::HalfUp),
+                Err(Error::InvalidArgument)
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_money_currency_mismatch() {
+        let message = GoogleMoney::from_money(Money(100), "EUR");
+        assert_eq!(
+            message.to_money("USD", Rounding::HalfUp),
+            Err(Error::CurrencyMismatch)
+        );
+    }
+
+    #[test]
+    fn test_wire_format() {
+        let bytes = [
+            0x0a, 3, b'U', b'S', b'D', // currency_code
+            0x10, 12, // units
+            0x18, 128, 250, 143, 162, 1, // nanos
+        ];
+        let message = message(12, 340_000_000);
+
+        assert_eq!(message.encode_to_vec(), bytes);
+        assert_eq!(GoogleMoney::decode(&bytes[..]).unwrap(), message);
+    }
+}
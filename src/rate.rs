@@ -0,0 +1,305 @@
+use std::fmt;
+use std::ops::Mul;
+
+use crate::{Error, Money, Rounding};
+
+/// An exact rational rate, such as an interest or tax rate.
+///
+/// Rates are kept as a reduced fraction so that applying one to Money never
+/// goes through floating point.
+///
+/// # Examples
+/// ```
+/// use postgres_money::{Money, Rate, Rounding};
+///
+/// // 4.125%
+/// let rate = Rate::new(4125, 100_000).unwrap();
+/// let money = Money::from(100000).mul_rate(rate, Rounding::HalfUp).unwrap();
+///
+/// assert_eq!("$41.25", money.to_string());
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Rate {
+    num: i64,
+    den: i64,
+}
+
+impl Rate {
+    /// Construct the rate `num / den`, returning `Error::InvalidArgument` if
+    /// `den` is zero
+    pub fn new(num: i64, den: i64) -> Result<Rate, Error> {
+        if den == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let divisor = gcd(i128::from(num), i128::from(den));
+        let sign = if den < 0 { -1 } else { 1 };
+        let num = i128::from(num) / divisor * sign;
+        let den = i128::from(den) / divisor * sign;
+
+        // Only `i64::MIN / -1` can fail to narrow back
+        match (i64::try_from(num), i64::try_from(den)) {
+            (Ok(num), Ok(den)) => Ok(Rate { num, den }),
+            _ => Err(Error::OutOfRange),
+        }
+    }
+
+    /// A rate of `percent` hundredths
+    pub fn percent(percent: i64) -> Rate {
+        Rate::new(percent, 100).unwrap()
+    }
+
+    /// A rate of `bps` ten-thousandths
+    pub fn basis_points(bps: i64) -> Rate {
+        Rate::new(bps, 10_000).unwrap()
+    }
+
+    /// A rate of zero
+    pub const fn zero() -> Rate {
+        Rate { num: 0, den: 1 }
+    }
+
+    /// A rate of one
+    pub const fn one() -> Rate {
+        Rate { num: 1, den: 1 }
+    }
+
+    /// Numerator of the reduced fraction
+    pub const fn numerator(&self) -> i64 {
+        self.num
+    }
+
+    /// Denominator of the reduced fraction, always positive
+    pub const fn denominator(&self) -> i64 {
+        self.den
+    }
+
+    /// Whether the rate is below zero
+    pub const fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+
+    /// The sum of two rates, returning `Error::OutOfRange` if it cannot be
+    /// represented
+    pub fn checked_add(self, rhs: Rate) -> Result<Rate, Error> {
+        let num =
+            i128::from(self.num) * i128::from(rhs.den) + i128::from(rhs.num) * i128::from(self.den);
+        let den = i128::from(self.den) * i128::from(rhs.den);
+        Rate::from_i128(num, den)
+    }
+
+    /// The product of two rates, returning `Error::OutOfRange` if it cannot
+    /// be represented
+    pub fn checked_mul(self, rhs: Rate) -> Result<Rate, Error> {
+        let num = i128::from(self.num) * i128::from(rhs.num);
+        let den = i128::from(self.den) * i128::from(rhs.den);
+        Rate::from_i128(num, den)
+    }
+
+    fn from_i128(num: i128, den: i128) -> Result<Rate, Error> {
+        let divisor = gcd(num, den);
+        match (i64::try_from(num / divisor), i64::try_from(den / divisor)) {
+            (Ok(num), Ok(den)) => Rate::new(num, den),
+            _ => Err(Error::OutOfRange),
+        }
+    }
+}
+
+impl fmt::Debug for Rate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+/// A whole number of percent
+///
+/// # Examples
+/// ```
+/// use postgres_money::{Money, Percent};
+///
+/// assert_eq!(Money::from(1999) * Percent(15), Money::from(300));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Percent(pub i64);
+
+/// A whole number of basis points, each one hundredth of a percent
+///
+/// # Examples
+/// ```
+/// use postgres_money::{BasisPoints, Money};
+///
+/// // 12.5%
+/// assert_eq!(Money::from(1999) * BasisPoints(1250), Money::from(250));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BasisPoints(pub i64);
+
+impl From<Percent> for Rate {
+    fn from(percent: Percent) -> Rate {
+        Rate::percent(percent.0)
+    }
+}
+
+impl From<BasisPoints> for Rate {
+    fn from(bps: BasisPoints) -> Rate {
+        Rate::basis_points(bps.0)
+    }
+}
+
+impl TryFrom<Percent> for BasisPoints {
+    type Error = Error;
+
+    fn try_from(percent: Percent) -> Result<BasisPoints, Error> {
+        percent
+            .0
+            .checked_mul(100)
+            .map(BasisPoints)
+            .ok_or(Error::OutOfRange)
+    }
+}
+
+impl Money {
+    /// Multiply by `rate`, rounding any fractional cent according to `rounding`.
+    ///
+    /// Returns `Error::OutOfRange` if the result does not fit in Money.
+    pub fn mul_rate(self, rate: impl Into<Rate>, rounding: Rounding) -> Result<Money, Error> {
+        let rate = rate.into();
+        let num = i128::from(self.inner()) * i128::from(rate.num);
+        Money::from_i128(rounding.div(num, i128::from(rate.den)))
+    }
+}
+
+// Like the other operators these panic on overflow. Fractional cents are
+// rounded half away from zero, as Postgres does.
+macro_rules! derive_mul_for_rate_type {
+    ($($t:ty)+) => ($(
+        impl Mul<$t> for Money {
+            type Output = Money;
+
+            fn mul(self, rhs: $t) -> Money {
+                self.mul_rate(rhs, Rounding::HalfUp)
+                    .expect("attempt to multiply with overflow")
+            }
+        }
+
+        impl Mul<Money> for $t {
+            type Output = Money;
+
+            fn mul(self, rhs: Money) -> Money {
+                rhs * self
+            }
+        }
+    )+)
+}
+
+derive_mul_for_rate_type! { Rate Percent BasisPoints }
+
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_reduces() {
+        let rate = Rate::new(50, 100).unwrap();
+        assert_eq!((rate.numerator(), rate.denominator()), (1, 2));
+        assert_eq!(rate, Rate::percent(50));
+    }
+
+    #[test]
+    fn test_new_normalises_sign() {
+        let rate = Rate::new(1, -4).unwrap();
+        assert_eq!((rate.numerator(), rate.denominator()), (-1, 4));
+        assert!(rate.is_negative());
+    }
+
+    #[test]
+    fn test_new_zero_denominator() {
+        assert_eq!(Rate::new(1, 0), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_new_unrepresentable() {
+        assert_eq!(Rate::new(i64::MIN, -1), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn test_zero_numerator() {
+        assert_eq!(Rate::new(0, 7), Ok(Rate::zero()));
+    }
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(
+            Rate::percent(5).checked_add(Rate::basis_points(25)),
+            Rate::new(525, 10_000)
+        );
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        assert_eq!(
+            Rate::percent(10).checked_mul(Rate::percent(50)),
+            Ok(Rate::percent(5))
+        );
+        assert_eq!(
+            Rate::new(1, i64::MAX)
+                .unwrap()
+                .checked_mul(Rate::new(1, 2).unwrap()),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_mul_rate_rounds() {
+        let third = Rate::new(1, 3).unwrap();
+        assert_eq!(Money(100).mul_rate(third, Rounding::HalfUp), Ok(Money(33)));
+        assert_eq!(Money(100).mul_rate(third, Rounding::Ceiling), Ok(Money(34)));
+        assert_eq!(Money(-100).mul_rate(third, Rounding::Floor), Ok(Money(-34)));
+    }
+
+    #[test]
+    fn test_percent_and_basis_points() {
+        assert_eq!(Rate::from(Percent(5)), Rate::basis_points(500));
+        assert_eq!(Rate::from(BasisPoints(1)), Rate::new(1, 10_000).unwrap());
+        assert_eq!(BasisPoints::try_from(Percent(7)), Ok(BasisPoints(700)));
+        assert_eq!(
+            BasisPoints::try_from(Percent(i64::MAX)),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_mul_operators() {
+        assert_eq!(Money(1005) * Percent(10), Money(101));
+        assert_eq!(Percent(10) * Money(1005), Money(101));
+        assert_eq!(Money(-1005) * BasisPoints(1000), Money(-101));
+        assert_eq!(Money(300) * Rate::new(1, 3).unwrap(), Money(100));
+    }
+
+    #[test]
+    #[should_panic]
+    #[allow(unused_must_use)]
+    fn test_mul_operator_overflow() {
+        Money::max() * Percent(101);
+    }
+
+    #[test]
+    fn test_mul_rate_exact_at_extremes() {
+        assert_eq!(
+            Money::max().mul_rate(Rate::one(), Rounding::HalfUp),
+            Ok(Money::max())
+        );
+        assert_eq!(
+            Money::min().mul_rate(Rate::percent(200), Rounding::HalfUp),
+            Err(Error::OutOfRange)
+        );
+    }
+}
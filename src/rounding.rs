@@ -0,0 +1,138 @@
+/// Strategy used when an exact result falls between two whole cents
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Rounding {
+    /// Round towards positive infinity
+    Ceiling,
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards zero (truncate)
+    Down,
+    /// Round away from zero
+    Up,
+    /// Round to nearest, ties away from zero. This matches how Postgres rounds
+    /// fractional cents when parsing `money` input.
+    #[default]
+    HalfUp,
+    /// Round to nearest, ties towards zero
+    HalfDown,
+    /// Round to nearest, ties to the even neighbour (banker's rounding)
+    HalfEven,
+}
+
+impl Rounding {
+    /// Divide `num` by `den` and round the quotient according to `self`.
+    ///
+    /// Panics if `den` is zero.
+    pub(crate) fn div(self, num: i128, den: i128) -> i128 {
+        let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+        let quotient = num / den;
+        let remainder = (num % den).abs();
+
+        if remainder == 0 {
+            return quotient;
+        }
+
+        let away = if num > 0 { quotient + 1 } else { quotient - 1 };
+        let toward = quotient;
+
+        // Compare the remainder against the other half of the divisor rather
+        // than doubling it, so that very large divisors cannot overflow.
+        let half = remainder.cmp(&(den - remainder));
+
+        match self {
+            Rounding::Ceiling => toward.max(away),
+            Rounding::Floor => toward.min(away),
+            Rounding::Down => toward,
+            Rounding::Up => away,
+            Rounding::HalfUp => {
+                if half.is_ge() {
+                    away
+                } else {
+                    toward
+                }
+            }
+            Rounding::HalfDown => {
+                if half.is_gt() {
+                    away
+                } else {
+                    toward
+                }
+            }
+            Rounding::HalfEven => {
+                if half.is_gt() || (half.is_eq() && toward % 2 != 0) {
+                    away
+                } else {
+                    toward
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rounding;
+
+    const ALL: [Rounding; 7] = [
+        Rounding::Ceiling,
+        Rounding::Floor,
+        Rounding::Down,
+        Rounding::Up,
+        Rounding::HalfUp,
+        Rounding::HalfDown,
+        Rounding::HalfEven,
+    ];
+
+    fn round_all(num: i128, den: i128) -> Vec<i128> {
+        ALL.iter().map(|r| r.div(num, den)).collect()
+    }
+
+    #[test]
+    fn test_exact_division_ignores_mode() {
+        assert_eq!(round_all(10, 5), vec![2; 7]);
+        assert_eq!(round_all(-10, 5), vec![-2; 7]);
+    }
+
+    #[test]
+    fn test_positive_tie() {
+        // 2.5
+        assert_eq!(round_all(5, 2), vec![3, 2, 2, 3, 3, 2, 2]);
+    }
+
+    #[test]
+    fn test_negative_tie() {
+        // -2.5
+        assert_eq!(round_all(-5, 2), vec![-2, -3, -2, -3, -3, -2, -2]);
+    }
+
+    #[test]
+    fn test_odd_tie_half_even() {
+        // 3.5
+        assert_eq!(Rounding::HalfEven.div(7, 2), 4);
+        assert_eq!(Rounding::HalfEven.div(-7, 2), -4);
+    }
+
+    #[test]
+    fn test_below_half() {
+        // 2.4
+        assert_eq!(round_all(12, 5), vec![3, 2, 2, 3, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_above_half() {
+        // -2.6
+        assert_eq!(round_all(-13, 5), vec![-2, -3, -2, -3, -3, -3, -3]);
+    }
+
+    #[test]
+    fn test_negative_divisor() {
+        assert_eq!(Rounding::Floor.div(5, -2), -3);
+        assert_eq!(Rounding::Ceiling.div(-5, -2), 3);
+    }
+
+    #[test]
+    fn test_huge_divisor() {
+        assert_eq!(Rounding::HalfUp.div(i128::MAX / 2 + 1, i128::MAX), 1);
+        assert_eq!(Rounding::HalfUp.div(i128::MAX / 2, i128::MAX), 0);
+    }
+}
@@ -0,0 +1,92 @@
+use rust_decimal::Decimal;
+
+use crate::{Error, Money, Rounding};
+
+impl Money {
+    /// Convert from a `rust_decimal::Decimal`, rounding anything past the
+    /// cents according to `rounding`.
+    ///
+    /// Returns `Error::OutOfRange` if the result does not fit in Money.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::{Money, Rounding};
+    /// use rust_decimal::Decimal;
+    ///
+    /// let decimal = Decimal::new(12345, 3);
+    /// let money = Money::from_decimal(decimal, Rounding::HalfEven).unwrap();
+    ///
+    /// assert_eq!(money, Money::from(1234));
+    /// ```
+    pub fn from_decimal(decimal: Decimal, rounding: Rounding) -> Result<Money, Error> {
+        // A 96-bit mantissa and a scale of at most 28 keep this within i128
+        let cents = decimal.mantissa() * 100;
+        let scale = 10i128.pow(decimal.scale());
+        Money::from_i128(rounding.div(cents, scale))
+    }
+}
+
+/// Rounds half away from zero, as Postgres does
+impl TryFrom<Decimal> for Money {
+    type Error = Error;
+
+    fn try_from(decimal: Decimal) -> Result<Money, Error> {
+        Money::from_decimal(decimal, Rounding::HalfUp)
+    }
+}
+
+/// Exact, with a scale of 2
+impl From<Money> for Decimal {
+    fn from(money: Money) -> Decimal {
+        Decimal::new(money.inner(), 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for money in [
+            Money::min(),
+            Money(-1),
+            Money(0),
+            Money(123456),
+            Money::max(),
+        ] {
+            let decimal = Decimal::from(money);
+            assert_eq!(decimal.scale(), 2);
+            assert_eq!(Money::try_from(decimal), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_from_decimal_scales() {
+        assert_eq!(Money::try_from(Decimal::new(7, 0)), Ok(Money(700)));
+        assert_eq!(Money::try_from(Decimal::new(75, 1)), Ok(Money(750)));
+        assert_eq!(Money::try_from(Decimal::new(-12345, 3)), Ok(Money(-1235)));
+        assert_eq!(
+            Money::from_decimal(Decimal::new(-12345, 3), Rounding::HalfEven),
+            Ok(Money(-1234))
+        );
+        assert_eq!(
+            Money::from_decimal(Decimal::new(1, 28), Rounding::Ceiling),
+            Ok(Money(1))
+        );
+    }
+
+    #[test]
+    fn test_from_decimal_out_of_range() {
+        assert_eq!(Money::try_from(Decimal::MAX), Err(Error::OutOfRange));
+        assert_eq!(
+            Money::try_from(Decimal::from(Money::max()) + Decimal::new(1, 2)),
+            Err(Error::OutOfRange)
+        );
+        // Rounds back into range
+        assert_eq!(
+            Money::try_from(Decimal::from(Money::max()) + Decimal::new(1, 3)),
+            Ok(Money::max())
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use sea_orm::sea_query::{
+    ArrayType, ColumnType, Nullable, OptionEnum, Value, ValueType, ValueTypeErr,
+};
+use sea_orm::{ColIdx, DbErr, QueryResult, RuntimeErr, TryGetError, TryGetable};
+
+use crate::{codec, BoundedMoney, Money};
+
+// sea-query has no money variant, and binding an int8 or numeric would let
+// Postgres cast it as whole dollars or reject comparisons. A typed enum value
+// is sent as text with an explicit `::"money"` cast instead.
+const TYPE_NAME: &str = "money";
+
+impl From<Money> for Value {
+    fn from(money: Money) -> Value {
+        Value::Enum(OptionEnum::Some(Box::new(sea_orm::sea_query::Enum {
+            type_name: TYPE_NAME.into(),
+            value: codec::encode_text(money).into(),
+        })))
+    }
+}
+
+impl Nullable for Money {
+    fn null() -> Value {
+        Value::Enum(OptionEnum::None(TYPE_NAME.into()))
+    }
+}
+
+impl ValueType for Money {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        match v {
+            Value::Enum(OptionEnum::Some(e)) if e.type_name.as_ref() == TYPE_NAME => {
+                codec::decode_text(&e.value).map_err(|_| ValueTypeErr)
+            }
+            Value::String(Some(s)) => codec::decode_text(&s).map_err(|_| ValueTypeErr),
+            _ => Err(ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "Money".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::Enum(Box::new(TYPE_NAME.into()))
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Money(None)
+    }
+}
+
+impl TryGetable for Money {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        // Postgres rows go through the SQLx decoder, which checks the column
+        // really is `money`; other backends store the text form
+        match res.try_as_pg_row() {
+            Some(row) => {
+                sqlx::Row::try_get::<Option<Money>, _>(row, index.as_sqlx_postgres_index())
+                    .map_err(|e| {
+                        TryGetError::DbErr(DbErr::Query(RuntimeErr::SqlxError(Arc::new(e))))
+                    })?
+                    .ok_or_else(|| TryGetError::Null(format!("{index:?}")))
+            }
+            None => {
+                let s = String::try_get_by(res, index)?;
+                codec::decode_text(&s).map_err(|e| TryGetError::DbErr(DbErr::Type(e.to_string())))
+            }
+        }
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> From<BoundedMoney<MIN, MAX>> for Value {
+    fn from(money: BoundedMoney<MIN, MAX>) -> Value {
+        money.get().into()
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Nullable for BoundedMoney<MIN, MAX> {
+    fn null() -> Value {
+        Money::null()
+    }
+}
+
+/// Conversion fails if the value is out of bounds
+impl<const MIN: i64, const MAX: i64> ValueType for BoundedMoney<MIN, MAX> {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        BoundedMoney::new(<Money as ValueType>::try_from(v)?).map_err(|_| ValueTypeErr)
+    }
+
+    fn type_name() -> String {
+        format!("BoundedMoney<{MIN}, {MAX}>")
+    }
+
+    fn array_type() -> ArrayType {
+        Money::array_type()
+    }
+
+    fn column_type() -> ColumnType {
+        Money::column_type()
+    }
+}
+
+/// Decoding fails if the value is out of bounds
+impl<const MIN: i64, const MAX: i64> TryGetable for BoundedMoney<MIN, MAX> {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<Self, TryGetError> {
+        BoundedMoney::new(Money::try_get_by(res, index)?)
+            .map_err(|e| TryGetError::DbErr(DbErr::Type(e.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::sea_query::{Alias, Expr, ExprTrait, PostgresQueryBuilder, Query};
+
+    use super::*;
+
+    #[test]
+    fn test_value_round_trip() {
+        for money in [Money::min(), Money(-123456), Money(0), Money::max()] {
+            assert_eq!(
+                <Money as ValueType>::try_from(Value::from(money)).unwrap(),
+                money
+            );
+        }
+    }
+
+    #[test]
+    fn test_value_from_string() {
+        let value = Value::String(Some("$1,234.56".to_owned()));
+        assert_eq!(Money::unwrap(value), Money(123456));
+        assert!(<Money as ValueType>::try_from(Value::BigInt(Some(1))).is_err());
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(<Option<Money> as ValueType>::unwrap(Money::null()), None);
+        assert_eq!(
+            <Option<Money> as ValueType>::unwrap(Value::from(Money(5))),
+            Some(Money(5))
+        );
+        assert_eq!(<Option<Money>>::type_name(), "Option<Money>");
+    }
+
+    #[test]
+    fn test_bounded_value() {
+        use crate::{NonNegativeMoney, PositiveMoney};
+
+        let money = PositiveMoney::new(Money(123456)).unwrap();
+        assert_eq!(PositiveMoney::unwrap(Value::from(money)), money);
+        assert_eq!(PositiveMoney::column_type(), ColumnType::Money(None));
+        assert_eq!(
+            <Option<NonNegativeMoney> as ValueType>::unwrap(NonNegativeMoney::null()),
+            None
+        );
+
+        let negative = Value::from(Money(-1));
+        assert!(<NonNegativeMoney as ValueType>::try_from(negative.clone()).is_err());
+        assert!(<PositiveMoney as ValueType>::try_from(Value::from(Money(0))).is_err());
+        assert!(<Option<NonNegativeMoney> as ValueType>::try_from(negative).is_err());
+    }
+
+    #[test]
+    fn test_column_type() {
+        assert_eq!(Money::column_type(), ColumnType::Money(None));
+        assert_eq!(<Option<Money>>::column_type(), ColumnType::Money(None));
+    }
+
+    #[test]
+    fn test_binds_with_cast() {
+        let (sql, values) = Query::select()
+            .column(Alias::new("id"))
+            .from(Alias::new("orders"))
+            .and_where(Expr::col(Alias::new("total")).gt(Money(-500)))
+            .build(PostgresQueryBuilder);
+
+        assert_eq!(
+            sql,
+            r#"SELECT "id" FROM "orders" WHERE "total" > $1::"money""#
+        );
+        assert_eq!(values.0, vec![Value::String(Some("-5.00".to_owned()))]);
+    }
+
+    #[test]
+    fn test_inline_literal() {
+        let sql = Query::insert()
+            .into_table(Alias::new("orders"))
+            .columns([Alias::new("total")])
+            .values_panic([Money(123456).into()])
+            .to_string(PostgresQueryBuilder);
+
+        assert_eq!(
+            sql,
+            r#"INSERT INTO "orders" ("total") VALUES ('1234.56'::"money")"#
+        );
+    }
+}
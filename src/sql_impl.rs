@@ -1,17 +1,12 @@
-use crate::Money;
+use crate::{codec, BoundedMoney, Money};
 
-use byteorder::{BigEndian, ReadBytesExt};
 use bytes::{BufMut, BytesMut};
 use postgres_types::{FromSql, IsNull, ToSql, Type};
 use std::error::Error;
 
 impl<'a> FromSql<'a> for Money {
-    fn from_sql(_: &Type, mut buf: &[u8]) -> Result<Money, Box<dyn Error + Sync + Send>> {
-        let v = buf.read_i64::<BigEndian>()?;
-        if !buf.is_empty() {
-            return Err("invalid buffer size".into());
-        }
-        Ok(Money::from(v))
+    fn from_sql(_: &Type, buf: &[u8]) -> Result<Money, Box<dyn Error + Sync + Send>> {
+        codec::decode(buf)
     }
 
     postgres_types::accepts!(MONEY);
@@ -19,10 +14,54 @@ impl<'a> FromSql<'a> for Money {
 
 impl ToSql for Money {
     fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
-        w.put_i64(self.inner());
+        w.put_slice(&codec::encode(*self));
         Ok(IsNull::No)
     }
 
     postgres_types::accepts!(MONEY);
     postgres_types::to_sql_checked!();
 }
+
+/// Decoding fails if the value is out of bounds
+impl<'a, const MIN: i64, const MAX: i64> FromSql<'a> for BoundedMoney<MIN, MAX> {
+    fn from_sql(_: &Type, buf: &[u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(BoundedMoney::new(codec::decode(buf)?)?)
+    }
+
+    postgres_types::accepts!(MONEY);
+}
+
+impl<const MIN: i64, const MAX: i64> ToSql for BoundedMoney<MIN, MAX> {
+    fn to_sql(&self, _: &Type, w: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        w.put_slice(&codec::encode(self.get()));
+        Ok(IsNull::No)
+    }
+
+    postgres_types::accepts!(MONEY);
+    postgres_types::to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NonNegativeMoney, PositiveMoney};
+
+    #[test]
+    fn test_bounded_round_trip() {
+        let money = PositiveMoney::new(Money(123456)).unwrap();
+        let mut buf = BytesMut::new();
+        money.to_sql(&Type::MONEY, &mut buf).unwrap();
+        assert_eq!(PositiveMoney::from_sql(&Type::MONEY, &buf).unwrap(), money);
+    }
+
+    #[test]
+    fn test_bounded_decode_validates() {
+        let buf = (-1i64).to_be_bytes();
+        let err = NonNegativeMoney::from_sql(&Type::MONEY, &buf).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<crate::Error>(),
+            Some(&crate::Error::BelowMinimum)
+        );
+        assert!(PositiveMoney::from_sql(&Type::MONEY, &buf[..4]).is_err());
+    }
+}
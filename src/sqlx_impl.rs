@@ -0,0 +1,184 @@
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::types::PgMoney;
+use sqlx::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use sqlx::types::Type;
+use sqlx::{Decode, Encode};
+
+use crate::{codec, BoundedMoney, Money};
+
+// SQLx's own cents wrapper carries the built-in type info, which avoids a
+// by-name lookup on every connection
+impl Type<Postgres> for Money {
+    fn type_info() -> PgTypeInfo {
+        PgMoney::type_info()
+    }
+}
+
+impl PgHasArrayType for Money {
+    fn array_type_info() -> PgTypeInfo {
+        PgMoney::array_type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for Money {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend(&codec::encode(*self));
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+impl Decode<'_, Postgres> for Money {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        decode(value.format(), value.as_bytes()?)
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Type<Postgres> for BoundedMoney<MIN, MAX> {
+    fn type_info() -> PgTypeInfo {
+        PgMoney::type_info()
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> PgHasArrayType for BoundedMoney<MIN, MAX> {
+    fn array_type_info() -> PgTypeInfo {
+        PgMoney::array_type_info()
+    }
+}
+
+impl<const MIN: i64, const MAX: i64> Encode<'_, Postgres> for BoundedMoney<MIN, MAX> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.get().encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+/// Decoding fails if the value is out of bounds
+impl<const MIN: i64, const MAX: i64> Decode<'_, Postgres> for BoundedMoney<MIN, MAX> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        decode_bounded(value.format(), value.as_bytes()?)
+    }
+}
+
+fn decode(format: PgValueFormat, buf: &[u8]) -> Result<Money, BoxDynError> {
+    match format {
+        PgValueFormat::Binary => codec::decode(buf),
+        PgValueFormat::Text => codec::decode_text(std::str::from_utf8(buf)?),
+    }
+}
+
+fn decode_bounded<const MIN: i64, const MAX: i64>(
+    format: PgValueFormat,
+    buf: &[u8],
+) -> Result<BoundedMoney<MIN, MAX>, BoxDynError> {
+    Ok(BoundedMoney::new(decode(format, buf)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Captured from `SELECT '1234.56'::money` and
+    // `SELECT '{1.00,-2.50}'::money[]` over the binary protocol
+    const BINARY_1234_56: [u8; 8] = [0, 0, 0, 0, 0, 1, 0xe2, 0x40];
+    const BINARY_ARRAY: [u8; 44] = [
+        0, 0, 0, 1, // dimensions
+        0, 0, 0, 0, // has nulls
+        0, 0, 3, 22, // element oid 790
+        0, 0, 0, 2, // length
+        0, 0, 0, 1, // lower bound
+        0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 100, // 1.00
+        0, 0, 0, 8, 255, 255, 255, 255, 255, 255, 255, 6, // -2.50
+    ];
+
+    fn encode<'q, T: Encode<'q, Postgres>>(value: T) -> Vec<u8> {
+        let mut buf = PgArgumentBuffer::default();
+        assert!(matches!(value.encode(&mut buf), Ok(IsNull::No)));
+        buf.to_vec()
+    }
+
+    #[test]
+    fn test_type_info() {
+        assert_eq!(Money::type_info().to_string(), "MONEY");
+        assert_eq!(Money::array_type_info().to_string(), "MONEY[]");
+        assert_eq!(
+            <Vec<Money> as Type<Postgres>>::type_info().to_string(),
+            "MONEY[]"
+        );
+    }
+
+    #[test]
+    fn test_decode_binary() {
+        assert_eq!(
+            decode(PgValueFormat::Binary, &BINARY_1234_56).unwrap(),
+            Money(123456)
+        );
+        assert!(decode(PgValueFormat::Binary, &BINARY_1234_56[..7]).is_err());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(
+            decode(PgValueFormat::Text, b"-$1,234.56").unwrap(),
+            Money(-123456)
+        );
+        assert!(decode(PgValueFormat::Text, b"$1.2.3").is_err());
+        assert!(decode(PgValueFormat::Text, &[0xff]).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(Money(123456)), BINARY_1234_56);
+        assert_eq!(encode(Money::max()), i64::MAX.to_be_bytes());
+    }
+
+    #[test]
+    fn test_encode_array() {
+        assert_eq!(encode(vec![Money(100), Money(-250)]), BINARY_ARRAY);
+        assert_eq!(encode(&[Money(100), Money(-250)][..]), BINARY_ARRAY);
+    }
+
+    #[test]
+    fn test_array_decodes() {
+        fn assert_decode<T: for<'r> Decode<'r, Postgres> + Type<Postgres>>() {}
+        assert_decode::<Vec<Money>>();
+        assert_decode::<Option<Money>>();
+        assert_decode::<Vec<crate::NonNegativeMoney>>();
+    }
+
+    #[test]
+    fn test_bounded() {
+        let money = crate::PositiveMoney::new(Money(123456)).unwrap();
+        assert_eq!(encode(money), BINARY_1234_56);
+        assert_eq!(crate::PositiveMoney::type_info().to_string(), "MONEY");
+    }
+
+    #[test]
+    fn test_bounded_decode_validates() {
+        let money: crate::PositiveMoney =
+            decode_bounded(PgValueFormat::Binary, &BINARY_1234_56).unwrap();
+        assert_eq!(money.get(), Money(123456));
+
+        let err = decode_bounded::<0, { i64::MAX }>(PgValueFormat::Binary, &(-1i64).to_be_bytes())
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<crate::Error>(),
+            Some(&crate::Error::BelowMinimum)
+        );
+
+        let err = decode_bounded::<0, 100>(PgValueFormat::Text, b"$1.01").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<crate::Error>(),
+            Some(&crate::Error::AboveMaximum)
+        );
+    }
+}
@@ -0,0 +1,602 @@
+//! Exact statistics over collections of [`Money`].
+//!
+//! Every function here works on the wrapped cent values with integer math, so
+//! results are never subject to floating point error. Where a result can fall
+//! between two cents (a mean, an even-length median, an interpolated quantile)
+//! the caller chooses how to round it with a [`Rounding`] mode.
+//!
+//! Functions accept anything that iterates over `Money` or `&Money`, so both
+//! slices and iterators can be passed directly.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::stats::{self, Histogram};
+//! use postgres_money::{Money, Rounding};
+//!
+//! let amounts = [Money::from(150), Money::from(275), Money::from(990), Money::from(1000)];
+//!
+//! assert_eq!(stats::median(&amounts, Rounding::HalfUp), Some(Money::from(633)));
+//!
+//! let mut histogram = Histogram::with_width(Money::none(), Money::from(500), 3).unwrap();
+//! histogram.extend(amounts);
+//!
+//! let counts: Vec<usize> = histogram.buckets().iter().map(|b| b.count).collect();
+//! assert_eq!(counts, vec![2, 1, 1]);
+//! ```
+
+use std::borrow::Borrow;
+
+use crate::{Error, Money, Rounding};
+
+/// A quantile expressed as an exact fraction between 0 and 1 inclusive
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Quantile {
+    num: u32,
+    den: u32,
+}
+
+impl Quantile {
+    /// The 50th percentile
+    pub const MEDIAN: Quantile = Quantile { num: 1, den: 2 };
+
+    /// Construct the quantile `num / den`.
+    ///
+    /// Returns `Error::InvalidArgument` if `den` is zero or `num` exceeds `den`.
+    pub fn new(num: u32, den: u32) -> Result<Quantile, Error> {
+        if den == 0 || num > den {
+            return Err(Error::InvalidArgument);
+        }
+        Ok(Quantile { num, den })
+    }
+
+    /// Construct the `p`th percentile, where `p` is between 0 and 100 inclusive
+    pub fn percentile(p: u32) -> Result<Quantile, Error> {
+        Quantile::new(p, 100)
+    }
+}
+
+/// How to pick a value when a quantile falls between two observations.
+///
+/// The variants mirror the methods offered by most statistics packages.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Interpolation {
+    /// Interpolate linearly between the two neighbouring observations
+    #[default]
+    Linear,
+    /// Take the lower of the two neighbouring observations
+    Lower,
+    /// Take the higher of the two neighbouring observations
+    Higher,
+    /// Take the observation nearest to the quantile's position. Ties are
+    /// resolved by applying the rounding mode to the position.
+    Nearest,
+    /// Take the average of the two neighbouring observations
+    Midpoint,
+}
+
+/// Sum all values, returning `Error::OutOfRange` if the total overflows
+pub fn sum<I>(values: I) -> Result<Money, Error>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Money>,
+{
+    let total = values
+        .into_iter()
+        .map(|m| i128::from(m.borrow().inner()))
+        .sum();
+    Money::from_i128(total)
+}
+
+/// Arithmetic mean of all values, or `None` if there are none
+pub fn mean<I>(values: I, rounding: Rounding) -> Option<Money>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Money>,
+{
+    let (total, count) = values
+        .into_iter()
+        .fold((0_i128, 0_i128), |(total, count), m| {
+            (total + i128::from(m.borrow().inner()), count + 1)
+        });
+
+    if count == 0 {
+        return None;
+    }
+    // The mean always lies between the smallest and largest value, so it fits
+    Some(Money(rounding.div(total, count) as i64))
+}
+
+/// Median of all values, or `None` if there are none.
+///
+/// For an even number of values this is the average of the middle two,
+/// rounded according to `rounding`.
+pub fn median<I>(values: I, rounding: Rounding) -> Option<Money>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Money>,
+{
+    quantile(values, Quantile::MEDIAN, Interpolation::Midpoint, rounding)
+}
+
+/// The `q` quantile of all values, or `None` if there are none.
+///
+/// # Examples
+/// ```
+/// use postgres_money::stats::{self, Interpolation, Quantile};
+/// use postgres_money::{Money, Rounding};
+///
+/// let amounts: Vec<Money> = (1..=10).map(|d| Money::from(d * 100)).collect();
+/// let p90 = Quantile::percentile(90).unwrap();
+///
+/// assert_eq!(
+///     stats::quantile(&amounts, p90, Interpolation::Linear, Rounding::HalfUp),
+///     Some(Money::from(910))
+/// );
+/// assert_eq!(
+///     stats::quantile(&amounts, p90, Interpolation::Lower, Rounding::HalfUp),
+///     Some(Money::from(900))
+/// );
+/// ```
+pub fn quantile<I>(
+    values: I,
+    q: Quantile,
+    interpolation: Interpolation,
+    rounding: Rounding,
+) -> Option<Money>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Money>,
+{
+    let sorted = sorted(values);
+    quantile_sorted(&sorted, q, interpolation, rounding)
+}
+
+/// Several quantiles of the same values, sorting them only once.
+///
+/// Returns `None` if there are no values.
+pub fn quantiles<I>(
+    values: I,
+    qs: &[Quantile],
+    interpolation: Interpolation,
+    rounding: Rounding,
+) -> Option<Vec<Money>>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Money>,
+{
+    let sorted = sorted(values);
+    qs.iter()
+        .map(|&q| quantile_sorted(&sorted, q, interpolation, rounding))
+        .collect()
+}
+
+fn sorted<I>(values: I) -> Vec<Money>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Money>,
+{
+    let mut sorted: Vec<Money> = values.into_iter().map(|m| *m.borrow()).collect();
+    sorted.sort_unstable();
+    sorted
+}
+
+fn quantile_sorted(
+    sorted: &[Money],
+    q: Quantile,
+    interpolation: Interpolation,
+    rounding: Rounding,
+) -> Option<Money> {
+    let last = sorted.len().checked_sub(1)?;
+
+    // The quantile sits at position `last * num / den`, split here into a
+    // whole index and a remainder over `den`.
+    let den = u128::from(q.den);
+    let position = last as u128 * u128::from(q.num);
+    let lower = (position / den) as usize;
+    let remainder = position % den;
+    let higher = if remainder == 0 { lower } else { lower + 1 };
+
+    let (lo, hi) = (i128::from(sorted[lower].0), i128::from(sorted[higher].0));
+    let (den, remainder) = (den as i128, remainder as i128);
+
+    let inner = match interpolation {
+        Interpolation::Lower => lo,
+        Interpolation::Higher => hi,
+        Interpolation::Nearest => {
+            let index = rounding.div(position as i128, den) as usize;
+            i128::from(sorted[index].0)
+        }
+        Interpolation::Midpoint => rounding.div(lo + hi, 2),
+        Interpolation::Linear => rounding.div(lo * den + (hi - lo) * remainder, den),
+    };
+
+    // Every interpolation lies between two existing values, so it fits
+    Some(Money(inner as i64))
+}
+
+/// A single half-open bucket `[lower, upper)` of a [`Histogram`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Bucket {
+    /// Inclusive lower bound
+    pub lower: Money,
+    /// Exclusive upper bound
+    pub upper: Money,
+    /// Number of values that fell into this bucket
+    pub count: usize,
+}
+
+/// Counts of values falling into contiguous, half-open buckets.
+///
+/// Values below the first bucket or at or above the last bucket's upper bound
+/// are counted separately as underflow and overflow.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Histogram {
+    buckets: Vec<Bucket>,
+    underflow: usize,
+    overflow: usize,
+}
+
+impl Histogram {
+    /// Create `count` buckets of equal `width`, the first starting at `start`.
+    ///
+    /// Returns `Error::InvalidArgument` if `width` is not positive or `count`
+    /// is zero, and `Error::OutOfRange` if the last bound does not fit in Money.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::stats::Histogram;
+    /// use postgres_money::Money;
+    ///
+    /// let histogram = Histogram::with_width(Money::from(-1000), Money::from(1000), 3).unwrap();
+    /// let bounds: Vec<(i64, i64)> = histogram
+    ///     .buckets()
+    ///     .iter()
+    ///     .map(|b| (b.lower.inner(), b.upper.inner()))
+    ///     .collect();
+    ///
+    /// assert_eq!(bounds, vec![(-1000, 0), (0, 1000), (1000, 2000)]);
+    /// ```
+    pub fn with_width(start: Money, width: Money, count: usize) -> Result<Histogram, Error> {
+        if width.inner() <= 0 || count == 0 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let start = i128::from(start.inner());
+        let width = i128::from(width.inner());
+        // Bound the last edge before allocating so a huge count fails cleanly
+        Money::from_i128(start + count as i128 * width)?;
+
+        let bounds: Vec<Money> = (0..=count as i128)
+            .map(|i| Money((start + i * width) as i64))
+            .collect();
+
+        Histogram::with_bounds(&bounds)
+    }
+
+    /// Create buckets between each pair of consecutive `bounds`.
+    ///
+    /// Returns `Error::InvalidArgument` unless there are at least two bounds
+    /// and they are strictly increasing.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::stats::Histogram;
+    /// use postgres_money::Money;
+    ///
+    /// let bounds = [Money::none(), Money::from(1000), Money::from(10000)];
+    /// let mut histogram = Histogram::with_bounds(&bounds).unwrap();
+    /// histogram.extend([Money::from(-1), Money::from(999), Money::from(1000), Money::from(10000)]);
+    ///
+    /// assert_eq!(histogram.underflow(), 1);
+    /// assert_eq!(histogram.buckets()[0].count, 1);
+    /// assert_eq!(histogram.buckets()[1].count, 1);
+    /// assert_eq!(histogram.overflow(), 1);
+    /// ```
+    pub fn with_bounds(bounds: &[Money]) -> Result<Histogram, Error> {
+        if bounds.len() < 2 || bounds.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(Error::InvalidArgument);
+        }
+
+        let buckets = bounds
+            .windows(2)
+            .map(|w| Bucket {
+                lower: w[0],
+                upper: w[1],
+                count: 0,
+            })
+            .collect();
+
+        Ok(Histogram {
+            buckets,
+            underflow: 0,
+            overflow: 0,
+        })
+    }
+
+    /// Count a single value
+    pub fn add(&mut self, value: Money) {
+        match self.bucket_index(value) {
+            Some(i) => self.buckets[i].count += 1,
+            None if value < self.buckets[0].lower => self.underflow += 1,
+            None => self.overflow += 1,
+        }
+    }
+
+    /// Index of the bucket `value` falls into, if any
+    pub fn bucket_index(&self, value: Money) -> Option<usize> {
+        let index = self.buckets.partition_point(|b| b.lower <= value);
+        let index = index.checked_sub(1)?;
+        if value < self.buckets[index].upper {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// The buckets in ascending order
+    pub fn buckets(&self) -> &[Bucket] {
+        &self.buckets
+    }
+
+    /// Number of values below the first bucket
+    pub fn underflow(&self) -> usize {
+        self.underflow
+    }
+
+    /// Number of values at or above the last bucket's upper bound
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+
+    /// Number of values counted, including underflow and overflow
+    pub fn total(&self) -> usize {
+        self.underflow + self.overflow + self.buckets.iter().map(|b| b.count).sum::<usize>()
+    }
+}
+
+impl Extend<Money> for Histogram {
+    fn extend<T: IntoIterator<Item = Money>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|m| self.add(m));
+    }
+}
+
+impl<'a> Extend<&'a Money> for Histogram {
+    fn extend<T: IntoIterator<Item = &'a Money>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|m| self.add(*m));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn money(cents: &[i64]) -> Vec<Money> {
+        cents.iter().map(|&c| Money(c)).collect()
+    }
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(sum(money(&[1, 2, 3])), Ok(Money(6)));
+        assert_eq!(sum(Vec::<Money>::new()), Ok(Money(0)));
+    }
+
+    #[test]
+    fn test_sum_recovers_from_intermediate_overflow() {
+        assert_eq!(sum([Money::max(), Money(1), Money(-1)]), Ok(Money::max()));
+    }
+
+    #[test]
+    fn test_sum_overflow() {
+        assert_eq!(sum([Money::max(), Money(1)]), Err(Error::OutOfRange));
+        assert_eq!(sum([Money::min(), Money(-1)]), Err(Error::OutOfRange));
+    }
+
+    #[test]
+    fn test_mean() {
+        assert_eq!(mean(money(&[1, 2]), Rounding::HalfUp), Some(Money(2)));
+        assert_eq!(mean(money(&[1, 2]), Rounding::HalfEven), Some(Money(2)));
+        assert_eq!(mean(money(&[1, 2]), Rounding::Down), Some(Money(1)));
+        assert_eq!(mean(Vec::<Money>::new(), Rounding::HalfUp), None);
+    }
+
+    #[test]
+    fn test_mean_extremes() {
+        assert_eq!(
+            mean([Money::max(), Money::max()], Rounding::HalfUp),
+            Some(Money::max())
+        );
+        assert_eq!(
+            mean([Money::min(), Money::min()], Rounding::HalfUp),
+            Some(Money::min())
+        );
+    }
+
+    #[test]
+    fn test_median_odd() {
+        assert_eq!(median(money(&[5, 1, 3]), Rounding::HalfUp), Some(Money(3)));
+    }
+
+    #[test]
+    fn test_median_even_rounds() {
+        assert_eq!(median(money(&[1, 2]), Rounding::HalfUp), Some(Money(2)));
+        assert_eq!(median(money(&[1, 2]), Rounding::Floor), Some(Money(1)));
+        assert_eq!(median(money(&[-1, -2]), Rounding::HalfUp), Some(Money(-2)));
+    }
+
+    #[test]
+    fn test_median_extremes() {
+        assert_eq!(
+            median([Money::min(), Money::max()], Rounding::Floor),
+            Some(Money(-1))
+        );
+    }
+
+    #[test]
+    fn test_median_empty() {
+        assert_eq!(median(Vec::<Money>::new(), Rounding::HalfUp), None);
+    }
+
+    #[test]
+    fn test_quantile_bounds() {
+        let values = money(&[30, 10, 20]);
+        let q0 = Quantile::new(0, 1).unwrap();
+        let q1 = Quantile::new(1, 1).unwrap();
+
+        assert_eq!(
+            quantile(&values, q0, Interpolation::Linear, Rounding::HalfUp),
+            Some(Money(10))
+        );
+        assert_eq!(
+            quantile(&values, q1, Interpolation::Linear, Rounding::HalfUp),
+            Some(Money(30))
+        );
+    }
+
+    #[test]
+    fn test_quantile_interpolations() {
+        // Position 0.25 * 4 = 1.0 exactly; 0.3 * 4 = 1.2
+        let values = money(&[0, 100, 200, 300, 400]);
+        let q = Quantile::new(3, 10).unwrap();
+        let at = |i| quantile(&values, q, i, Rounding::HalfUp);
+
+        assert_eq!(at(Interpolation::Linear), Some(Money(120)));
+        assert_eq!(at(Interpolation::Lower), Some(Money(100)));
+        assert_eq!(at(Interpolation::Higher), Some(Money(200)));
+        assert_eq!(at(Interpolation::Nearest), Some(Money(100)));
+        assert_eq!(at(Interpolation::Midpoint), Some(Money(150)));
+
+        let q = Quantile::new(1, 4).unwrap();
+        for i in [
+            Interpolation::Linear,
+            Interpolation::Lower,
+            Interpolation::Higher,
+            Interpolation::Nearest,
+            Interpolation::Midpoint,
+        ] {
+            assert_eq!(quantile(&values, q, i, Rounding::HalfUp), Some(Money(100)));
+        }
+    }
+
+    #[test]
+    fn test_quantile_nearest_tie_uses_rounding() {
+        let values = money(&[10, 20]);
+        let at = |r| quantile(&values, Quantile::MEDIAN, Interpolation::Nearest, r);
+
+        assert_eq!(at(Rounding::HalfUp), Some(Money(20)));
+        assert_eq!(at(Rounding::HalfDown), Some(Money(10)));
+    }
+
+    #[test]
+    fn test_quantile_linear_rounding() {
+        // 1/3 of the way from 0 to 1 cent
+        let values = money(&[0, 1, 2, 3]);
+        let q = Quantile::new(1, 9).unwrap();
+
+        assert_eq!(
+            quantile(&values, q, Interpolation::Linear, Rounding::HalfUp),
+            Some(Money(0))
+        );
+        assert_eq!(
+            quantile(&values, q, Interpolation::Linear, Rounding::Ceiling),
+            Some(Money(1))
+        );
+    }
+
+    #[test]
+    fn test_quantile_linear_extremes() {
+        let values = [Money::min(), Money::max()];
+        let q = Quantile::new(u32::MAX - 1, u32::MAX).unwrap();
+
+        assert!(quantile(values, q, Interpolation::Linear, Rounding::HalfUp).is_some());
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let values = money(&[4, 1, 3, 2]);
+        let qs = [
+            Quantile::new(0, 1).unwrap(),
+            Quantile::MEDIAN,
+            Quantile::new(1, 1).unwrap(),
+        ];
+
+        assert_eq!(
+            quantiles(&values, &qs, Interpolation::Lower, Rounding::HalfUp),
+            Some(money(&[1, 2, 4]))
+        );
+        assert_eq!(
+            quantiles(
+                Vec::<Money>::new(),
+                &qs,
+                Interpolation::Lower,
+                Rounding::HalfUp
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_quantile_invalid() {
+        assert_eq!(Quantile::new(1, 0), Err(Error::InvalidArgument));
+        assert_eq!(Quantile::new(2, 1), Err(Error::InvalidArgument));
+        assert_eq!(Quantile::percentile(101), Err(Error::InvalidArgument));
+    }
+
+    #[test]
+    fn test_histogram_width() {
+        let mut histogram = Histogram::with_width(Money(0), Money(10), 3).unwrap();
+        histogram.extend(&money(&[-1, 0, 9, 10, 29, 30]));
+
+        let counts: Vec<usize> = histogram.buckets().iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![2, 1, 1]);
+        assert_eq!(histogram.underflow(), 1);
+        assert_eq!(histogram.overflow(), 1);
+        assert_eq!(histogram.total(), 6);
+    }
+
+    #[test]
+    fn test_histogram_width_invalid() {
+        assert_eq!(
+            Histogram::with_width(Money(0), Money(0), 3),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Histogram::with_width(Money(0), Money(10), 0),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Histogram::with_width(Money::max(), Money(1), 1),
+            Err(Error::OutOfRange)
+        );
+        assert_eq!(
+            Histogram::with_width(Money::none(), Money(1), usize::MAX),
+            Err(Error::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_histogram_bounds_invalid() {
+        assert_eq!(
+            Histogram::with_bounds(&money(&[1])),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Histogram::with_bounds(&money(&[1, 1])),
+            Err(Error::InvalidArgument)
+        );
+        assert_eq!(
+            Histogram::with_bounds(&money(&[2, 1])),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_histogram_bucket_index() {
+        let histogram = Histogram::with_bounds(&money(&[0, 5, 100])).unwrap();
+
+        assert_eq!(histogram.bucket_index(Money(-1)), None);
+        assert_eq!(histogram.bucket_index(Money(0)), Some(0));
+        assert_eq!(histogram.bucket_index(Money(5)), Some(1));
+        assert_eq!(histogram.bucket_index(Money(99)), Some(1));
+        assert_eq!(histogram.bucket_index(Money(100)), None);
+    }
+}
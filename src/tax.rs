@@ -0,0 +1,479 @@
+//! Tax calculation over invoice lines.
+//!
+//! Taxes are applied with exact [`Rate`] arithmetic and rounded to whole cents
+//! only once, either for every line or for the invoice as a whole. Either way
+//! the returned [`TaxBreakdown`] reconciles exactly: every line's gross is its
+//! net plus its taxes, and the invoice totals are the sums of the lines.
+//!
+//! # Examples
+//! ```
+//! use postgres_money::tax::{Pricing, Tax, TaxCalculator, TaxRounding};
+//! use postgres_money::{Money, Rate, Rounding};
+//!
+//! let calculator = TaxCalculator::new(
+//!     vec![Tax::new("VAT", Rate::percent(20))],
+//!     Pricing::Inclusive,
+//!     TaxRounding::PerInvoice,
+//!     Rounding::HalfUp,
+//! )
+//! .unwrap();
+//! let breakdown = calculator
+//!     .calculate(&[Money::from(999), Money::from(999), Money::from(999)])
+//!     .unwrap();
+//!
+//! assert_eq!("$29.97", breakdown.gross.to_string());
+//! assert_eq!("$5.00", breakdown.taxes[0].to_string());
+//! assert_eq!("$24.97", breakdown.net.to_string());
+//! ```
+
+use crate::{Error, Money, Rate, Rounding};
+
+/// A single named tax
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Tax {
+    /// Label for the tax, such as `"VAT"`
+    pub name: String,
+    /// Rate applied to the taxable base
+    pub rate: Rate,
+    /// Whether the taxable base includes the taxes listed before this one
+    pub compound: bool,
+}
+
+impl Tax {
+    /// A tax levied on the net amount only
+    pub fn new(name: impl Into<String>, rate: Rate) -> Tax {
+        Tax {
+            name: name.into(),
+            rate,
+            compound: false,
+        }
+    }
+
+    /// A tax levied on the net amount plus every tax listed before it
+    pub fn compound(name: impl Into<String>, rate: Rate) -> Tax {
+        Tax {
+            name: name.into(),
+            rate,
+            compound: true,
+        }
+    }
+}
+
+/// Whether line amounts already include tax
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Pricing {
+    /// Line amounts are net; taxes are added on top
+    #[default]
+    Exclusive,
+    /// Line amounts are gross; taxes are extracted from them
+    Inclusive,
+}
+
+/// Where rounding to whole cents happens
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum TaxRounding {
+    /// Round each tax on each line, then sum the rounded amounts
+    #[default]
+    PerLine,
+    /// Sum the exact tax across all lines and round each tax once. The rounded
+    /// total is then spread back over the lines by largest remainder.
+    PerInvoice,
+}
+
+/// Taxes for one invoice line
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LineTax {
+    /// Amount before tax
+    pub net: Money,
+    /// Amount of each tax, in the order the taxes were given
+    pub taxes: Vec<Money>,
+    /// Amount including tax
+    pub gross: Money,
+}
+
+/// Taxes for a whole invoice
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TaxBreakdown {
+    /// Per-line detail, in the order the lines were given
+    pub lines: Vec<LineTax>,
+    /// Total net amount
+    pub net: Money,
+    /// Total of each tax, in the order the taxes were given
+    pub taxes: Vec<Money>,
+    /// Total amount including tax
+    pub gross: Money,
+}
+
+impl TaxBreakdown {
+    /// Sum of every tax on the invoice
+    pub fn total_tax(&self) -> Result<Money, Error> {
+        crate::stats::sum(&self.taxes)
+    }
+}
+
+/// Applies a fixed list of taxes to invoice lines
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TaxCalculator {
+    taxes: Vec<Tax>,
+    pricing: Pricing,
+    tax_rounding: TaxRounding,
+    rounding: Rounding,
+    /// For each tax, the fraction of a line amount it accounts for
+    shares: Vec<Rate>,
+}
+
+impl TaxCalculator {
+    /// Create a calculator applying `taxes` in order.
+    ///
+    /// Returns `Error::InvalidArgument` if any rate is negative, and
+    /// `Error::OutOfRange` if the combined rates cannot be represented exactly.
+    pub fn new(
+        taxes: Vec<Tax>,
+        pricing: Pricing,
+        tax_rounding: TaxRounding,
+        rounding: Rounding,
+    ) -> Result<TaxCalculator, Error> {
+        if taxes.iter().any(|t| t.rate.is_negative()) {
+            return Err(Error::InvalidArgument);
+        }
+
+        // Each tax as a fraction of the net amount. A compound tax also
+        // applies to the fractions of the taxes before it.
+        let mut shares = Vec::with_capacity(taxes.len());
+        let mut multiplier = Rate::one();
+        for tax in &taxes {
+            let share = if tax.compound {
+                tax.rate.checked_mul(multiplier)?
+            } else {
+                tax.rate
+            };
+            multiplier = multiplier.checked_add(share)?;
+            shares.push(share);
+        }
+
+        // With tax-inclusive pricing the line amount is the net amount times
+        // the multiplier, so re-express each share relative to that instead
+        if pricing == Pricing::Inclusive {
+            let inverse = Rate::new(multiplier.denominator(), multiplier.numerator())?;
+            shares = shares
+                .into_iter()
+                .map(|share| share.checked_mul(inverse))
+                .collect::<Result<_, _>>()?;
+        }
+
+        Ok(TaxCalculator {
+            taxes,
+            pricing,
+            tax_rounding,
+            rounding,
+            shares,
+        })
+    }
+
+    /// The taxes applied, in order
+    pub fn taxes(&self) -> &[Tax] {
+        &self.taxes
+    }
+
+    /// Calculate the taxes on `lines`.
+    ///
+    /// Returns `Error::OutOfRange` if any amount or total does not fit in Money.
+    ///
+    /// # Examples
+    /// ```
+    /// use postgres_money::tax::{Pricing, Tax, TaxCalculator, TaxRounding};
+    /// use postgres_money::{Money, Rate, Rounding};
+    ///
+    /// let taxes = vec![
+    ///     Tax::new("GST", Rate::percent(5)),
+    ///     Tax::compound("PST", Rate::new(95, 1000).unwrap()),
+    /// ];
+    /// let calculator = TaxCalculator::new(taxes, Pricing::Exclusive, TaxRounding::PerLine, Rounding::HalfUp).unwrap();
+    /// let breakdown = calculator.calculate(&[Money::from(10000)]).unwrap();
+    ///
+    /// assert_eq!("$5.00", breakdown.taxes[0].to_string());
+    /// assert_eq!("$9.98", breakdown.taxes[1].to_string());
+    /// assert_eq!("$114.98", breakdown.gross.to_string());
+    /// ```
+    pub fn calculate(&self, lines: &[Money]) -> Result<TaxBreakdown, Error> {
+        // Exact tax for line `l` and tax `t` is `lines[l] * shares[t]`
+        let line_taxes: Vec<Vec<Money>> = match self.tax_rounding {
+            TaxRounding::PerLine => lines
+                .iter()
+                .map(|&amount| {
+                    self.shares
+                        .iter()
+                        .map(|&share| amount.mul_rate(share, self.rounding))
+                        .collect()
+                })
+                .collect::<Result<_, _>>()?,
+            TaxRounding::PerInvoice => {
+                let total = crate::stats::sum(lines)?;
+                let columns = self
+                    .shares
+                    .iter()
+                    .map(|&share| {
+                        let rounded = total.mul_rate(share, self.rounding)?;
+                        allocate(rounded, lines, share)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                transpose(columns, lines.len())
+            }
+        };
+
+        let lines = lines
+            .iter()
+            .zip(line_taxes)
+            .map(|(&amount, taxes)| {
+                let tax = crate::stats::sum(&taxes)?;
+                let (net, gross) = match self.pricing {
+                    Pricing::Exclusive => (amount, amount.checked_add(tax)),
+                    Pricing::Inclusive => (
+                        amount.checked_sub(tax).ok_or(Error::OutOfRange)?,
+                        Some(amount),
+                    ),
+                };
+                Ok(LineTax {
+                    net,
+                    taxes,
+                    gross: gross.ok_or(Error::OutOfRange)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let taxes = (0..self.shares.len())
+            .map(|t| crate::stats::sum(lines.iter().map(|l| l.taxes[t])))
+            .collect::<Result<_, _>>()?;
+
+        Ok(TaxBreakdown {
+            net: crate::stats::sum(lines.iter().map(|l| l.net))?,
+            gross: crate::stats::sum(lines.iter().map(|l| l.gross))?,
+            taxes,
+            lines,
+        })
+    }
+}
+
+/// Split `total` across `lines` in proportion to `lines[l] * share`.
+///
+/// Each line first gets its exact amount rounded down, then the cents left
+/// over go one at a time to the lines with the largest remainders. `total`
+/// must be a rounding of the exact sum, which bounds the leftover cents
+/// between zero and the number of lines.
+fn allocate(total: Money, lines: &[Money], share: Rate) -> Result<Vec<Money>, Error> {
+    let den = i128::from(share.denominator());
+    let exact: Vec<i128> = lines
+        .iter()
+        .map(|m| i128::from(m.inner()) * i128::from(share.numerator()))
+        .collect();
+
+    let mut allocated = exact
+        .iter()
+        .map(|n| Money::from_i128(n.div_euclid(den)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let floors = crate::stats::sum(&allocated)?;
+    let leftover = (total.inner() - floors.inner()) as usize;
+
+    let mut by_remainder: Vec<usize> = (0..lines.len()).collect();
+    by_remainder.sort_by_key(|&l| std::cmp::Reverse(exact[l].rem_euclid(den)));
+    for &l in by_remainder.iter().take(leftover) {
+        allocated[l] = allocated[l] + Money(1);
+    }
+
+    Ok(allocated)
+}
+
+fn transpose(columns: Vec<Vec<Money>>, rows: usize) -> Vec<Vec<Money>> {
+    (0..rows)
+        .map(|r| columns.iter().map(|c| c[r]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculator(taxes: Vec<Tax>, pricing: Pricing, tax_rounding: TaxRounding) -> TaxCalculator {
+        TaxCalculator::new(taxes, pricing, tax_rounding, Rounding::HalfUp).unwrap()
+    }
+
+    fn money(cents: &[i64]) -> Vec<Money> {
+        cents.iter().map(|&c| Money(c)).collect()
+    }
+
+    fn assert_reconciles(breakdown: &TaxBreakdown) {
+        for line in &breakdown.lines {
+            let tax: i64 = line.taxes.iter().map(|t| t.inner()).sum();
+            assert_eq!(line.net.inner() + tax, line.gross.inner());
+        }
+        let tax: i64 = breakdown.taxes.iter().map(|t| t.inner()).sum();
+        assert_eq!(breakdown.net.inner() + tax, breakdown.gross.inner());
+        assert_eq!(breakdown.total_tax(), Ok(Money(tax)));
+    }
+
+    #[test]
+    fn test_exclusive_per_line() {
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Exclusive,
+            TaxRounding::PerLine,
+        );
+        let b = c.calculate(&money(&[333, 333, 333])).unwrap();
+
+        // 66.6 cents rounds up on every line
+        assert_eq!(b.lines[0].taxes, money(&[67]));
+        assert_eq!(b.taxes, money(&[201]));
+        assert_eq!(b.net, Money(999));
+        assert_eq!(b.gross, Money(1200));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_exclusive_per_invoice() {
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Exclusive,
+            TaxRounding::PerInvoice,
+        );
+        let b = c.calculate(&money(&[333, 333, 333])).unwrap();
+
+        // 199.8 cents rounds to 200 overall
+        assert_eq!(b.taxes, money(&[200]));
+        let line_taxes: Vec<Money> = b.lines.iter().map(|l| l.taxes[0]).collect();
+        assert_eq!(line_taxes, money(&[67, 67, 66]));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_inclusive_per_line() {
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Inclusive,
+            TaxRounding::PerLine,
+        );
+        let b = c.calculate(&money(&[999, 999, 999])).unwrap();
+
+        // 999 / 6 = 166.5 rounds up on every line
+        assert_eq!(b.lines[0].taxes, money(&[167]));
+        assert_eq!(b.lines[0].net, Money(832));
+        assert_eq!(b.gross, Money(2997));
+        assert_eq!(b.taxes, money(&[501]));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_inclusive_per_invoice() {
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Inclusive,
+            TaxRounding::PerInvoice,
+        );
+        let b = c.calculate(&money(&[999, 999, 999])).unwrap();
+
+        assert_eq!(b.taxes, money(&[500]));
+        assert_eq!(b.net, Money(2497));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_compound_exclusive() {
+        let c = calculator(
+            vec![
+                Tax::new("GST", Rate::percent(5)),
+                Tax::compound("QST", Rate::percent(10)),
+            ],
+            Pricing::Exclusive,
+            TaxRounding::PerLine,
+        );
+        let b = c.calculate(&money(&[10000])).unwrap();
+
+        assert_eq!(b.taxes, money(&[500, 1050]));
+        assert_eq!(b.gross, Money(11550));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_compound_inclusive_recovers_exclusive() {
+        let taxes = vec![
+            Tax::new("GST", Rate::percent(5)),
+            Tax::compound("QST", Rate::percent(10)),
+        ];
+        let c = calculator(taxes, Pricing::Inclusive, TaxRounding::PerLine);
+        let b = c.calculate(&money(&[11550])).unwrap();
+
+        assert_eq!(b.net, Money(10000));
+        assert_eq!(b.taxes, money(&[500, 1050]));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_multiple_simple_taxes() {
+        let c = calculator(
+            vec![
+                Tax::new("State", Rate::basis_points(625)),
+                Tax::new("County", Rate::basis_points(100)),
+            ],
+            Pricing::Exclusive,
+            TaxRounding::PerInvoice,
+        );
+        let b = c.calculate(&money(&[1999, 2999, 499])).unwrap();
+
+        // 5497 * 6.25% = 343.5625, 5497 * 1% = 54.97
+        assert_eq!(b.taxes, money(&[344, 55]));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_credit_lines() {
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Exclusive,
+            TaxRounding::PerInvoice,
+        );
+        let b = c.calculate(&money(&[1001, -1001, 3])).unwrap();
+
+        assert_eq!(b.taxes, money(&[1]));
+        assert_reconciles(&b);
+    }
+
+    #[test]
+    fn test_no_lines_and_no_taxes() {
+        let c = calculator(vec![], Pricing::Exclusive, TaxRounding::PerInvoice);
+        let b = c.calculate(&money(&[100])).unwrap();
+        assert_eq!(b.gross, Money(100));
+        assert!(b.taxes.is_empty());
+
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Exclusive,
+            TaxRounding::PerInvoice,
+        );
+        let b = c.calculate(&[]).unwrap();
+        assert_eq!(b.taxes, money(&[0]));
+        assert!(b.lines.is_empty());
+    }
+
+    #[test]
+    fn test_negative_rate() {
+        assert_eq!(
+            TaxCalculator::new(
+                vec![Tax::new("Bad", Rate::percent(-1))],
+                Pricing::Exclusive,
+                TaxRounding::PerLine,
+                Rounding::HalfUp
+            ),
+            Err(Error::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let c = calculator(
+            vec![Tax::new("VAT", Rate::percent(20))],
+            Pricing::Exclusive,
+            TaxRounding::PerLine,
+        );
+        assert_eq!(c.calculate(&[Money::max()]), Err(Error::OutOfRange));
+    }
+}
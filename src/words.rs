@@ -0,0 +1,281 @@
+//! Amounts spelled out in words, as written on cheques and in contracts.
+//!
+//! [Money::to_words] renders an amount in any [Language]. English is built
+//! in; other languages implement the trait on top of [Money::parts].
+//!
+//! # Examples
+//! ```
+//! use postgres_money::words::{CentsStyle, English};
+//! use postgres_money::Money;
+//!
+//! let money = Money::from(123456);
+//! assert_eq!(
+//!     money.to_words(&English::default()),
+//!     "One thousand two hundred thirty-four and 56/100 dollars"
+//! );
+//! assert_eq!(
+//!     money.to_words(&English::new(CentsStyle::Words)),
+//!     "One thousand two hundred thirty-four dollars and fifty-six cents"
+//! );
+//! ```
+
+use std::fmt::Write;
+
+use crate::{Money, MoneyParts};
+
+/// A language that amounts can be spelled out in
+pub trait Language {
+    /// The words for the amount in `parts`
+    fn words(&self, parts: MoneyParts) -> String;
+}
+
+/// How the cents of an amount are written
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum CentsStyle {
+    /// As a fraction of a dollar, as on a cheque: "and 56/100 dollars"
+    #[default]
+    Fraction,
+    /// In words: "dollars and fifty-six cents"
+    Words,
+}
+
+/// English words in US usage, such as "One thousand two hundred thirty-four
+/// and 56/100 dollars".
+///
+/// Negative amounts start with "Minus".
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct English {
+    /// How cents are written
+    pub cents: CentsStyle,
+}
+
+impl English {
+    /// English words with the given cents style
+    pub const fn new(cents: CentsStyle) -> English {
+        English { cents }
+    }
+}
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [&str; 7] = [
+    "",
+    " thousand",
+    " million",
+    " billion",
+    " trillion",
+    " quadrillion",
+    " quintillion",
+];
+
+/// Append the English words for `n`, which is below 1000 and nonzero
+fn push_hundreds(out: &mut String, n: u64) {
+    let (hundreds, rest) = (n / 100, n % 100);
+    if hundreds > 0 {
+        out.push_str(ONES[hundreds as usize]);
+        out.push_str(" hundred");
+        if rest > 0 {
+            out.push(' ');
+        }
+    }
+
+    match rest {
+        0 => {}
+        1..=19 => out.push_str(ONES[rest as usize]),
+        _ => {
+            out.push_str(TENS[(rest / 10) as usize]);
+            if rest % 10 > 0 {
+                out.push('-');
+                out.push_str(ONES[(rest % 10) as usize]);
+            }
+        }
+    }
+}
+
+/// Append the English words for `n`
+fn push_number(out: &mut String, n: u64) {
+    if n == 0 {
+        out.push_str(ONES[0]);
+        return;
+    }
+
+    let mut groups = [0; 7];
+    let mut rest = n;
+    for group in &mut groups {
+        *group = rest % 1000;
+        rest /= 1000;
+    }
+
+    let mut first = true;
+    for (scale, &group) in groups.iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        if !first {
+            out.push(' ');
+        }
+        push_hundreds(out, group);
+        out.push_str(SCALES[scale]);
+        first = false;
+    }
+}
+
+fn push_unit(out: &mut String, n: u64, singular: &str, plural: &str) {
+    push_number(out, n);
+    out.push(' ');
+    out.push_str(if n == 1 { singular } else { plural });
+}
+
+impl Language for English {
+    fn words(&self, parts: MoneyParts) -> String {
+        let mut out = String::new();
+        if parts.negative {
+            out.push_str("minus ");
+        }
+
+        match self.cents {
+            CentsStyle::Fraction => {
+                push_number(&mut out, parts.major);
+                write!(out, " and {:02}/100 dollars", parts.minor).unwrap();
+            }
+            CentsStyle::Words => {
+                let minor = u64::from(parts.minor);
+                if parts.major > 0 || minor == 0 {
+                    push_unit(&mut out, parts.major, "dollar", "dollars");
+                    if minor > 0 {
+                        out.push_str(" and ");
+                    }
+                }
+                if minor > 0 {
+                    push_unit(&mut out, minor, "cent", "cents");
+                }
+            }
+        }
+
+        // Every word above starts with an ASCII letter
+        out[..1].make_ascii_uppercase();
+        out
+    }
+}
+
+impl Money {
+    /// Spell out the amount in `language`
+    pub fn to_words<L: Language + ?Sized>(self, language: &L) -> String {
+        language.words(self.parts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHEQUE: English = English::new(CentsStyle::Fraction);
+    const WORDS: English = English::new(CentsStyle::Words);
+
+    #[test]
+    fn test_cheque_style() {
+        assert_eq!(
+            Money(123456).to_words(&CHEQUE),
+            "One thousand two hundred thirty-four and 56/100 dollars"
+        );
+        assert_eq!(Money(0).to_words(&CHEQUE), "Zero and 00/100 dollars");
+        assert_eq!(Money(7).to_words(&CHEQUE), "Zero and 07/100 dollars");
+        assert_eq!(
+            Money(-100).to_words(&CHEQUE),
+            "Minus one and 00/100 dollars"
+        );
+    }
+
+    #[test]
+    fn test_words_style() {
+        assert_eq!(
+            Money(123456).to_words(&WORDS),
+            "One thousand two hundred thirty-four dollars and fifty-six cents"
+        );
+        assert_eq!(Money(0).to_words(&WORDS), "Zero dollars");
+        assert_eq!(Money(1).to_words(&WORDS), "One cent");
+        assert_eq!(
+            Money(-101).to_words(&WORDS),
+            "Minus one dollar and one cent"
+        );
+        assert_eq!(Money(2000).to_words(&WORDS), "Twenty dollars");
+    }
+
+    #[test]
+    fn test_numbers() {
+        let words = |n| {
+            let mut out = String::new();
+            push_number(&mut out, n);
+            out
+        };
+
+        assert_eq!(words(11), "eleven");
+        assert_eq!(words(90), "ninety");
+        assert_eq!(words(105), "one hundred five");
+        assert_eq!(words(1_000_001), "one million one");
+        assert_eq!(words(2_030_000), "two million thirty thousand");
+        assert_eq!(
+            words(u64::MAX),
+            "eighteen quintillion four hundred forty-six quadrillion \
+             seven hundred forty-four trillion seventy-three billion \
+             seven hundred nine million five hundred fifty-one thousand \
+             six hundred fifteen"
+        );
+    }
+
+    #[test]
+    fn test_extremes() {
+        let quadrillions = "ninety-two quadrillion two hundred thirty-three trillion \
+                            seven hundred twenty billion three hundred sixty-eight million \
+                            five hundred forty-seven thousand seven hundred fifty-eight";
+
+        assert_eq!(
+            Money::max().to_words(&CHEQUE),
+            format!("N{} and 07/100 dollars", &quadrillions[1..])
+        );
+        assert_eq!(
+            Money::min().to_words(&WORDS),
+            format!("Minus {quadrillions} dollars and eight cents")
+        );
+    }
+
+    struct Digits;
+
+    impl Language for Digits {
+        fn words(&self, parts: MoneyParts) -> String {
+            format!("{}/{}/{}", parts.negative, parts.major, parts.minor)
+        }
+    }
+
+    #[test]
+    fn test_custom_language() {
+        assert_eq!(Money(-1234).to_words(&Digits), "true/12/34");
+        let dynamic: &dyn Language = &Digits;
+        assert_eq!(Money(5).to_words(dynamic), "false/0/5");
+    }
+}
@@ -0,0 +1,29 @@
+-- Regenerates the Postgres corpora replayed by the parser tests.
+--
+-- Run from this directory against any Postgres server:
+--
+--     psql -X -q -f capture.sql
+--
+-- cash_in.tsv pairs each line of cash_in_inputs.txt with the output of
+-- `input::money::text`, or `!invalid` / `!out_of_range` for the error raised.
+-- cash_out.tsv pairs amounts in cents with the text Postgres shows for them.
+-- The C monetary locale formats money just as en_US.UTF-8 does, and is
+-- available on every server.
+
+SET lc_monetary = 'C';
+
+CREATE TEMP TABLE inputs (n serial, input text);
+\copy inputs (input) FROM 'cash_in_inputs.txt'
+
+CREATE FUNCTION pg_temp.cash_in_out(input text) RETURNS text AS $$
+BEGIN
+    RETURN input::money::text;
+EXCEPTION
+    WHEN invalid_text_representation THEN RETURN '!invalid';
+    WHEN numeric_value_out_of_range THEN RETURN '!out_of_range';
+END
+$$ LANGUAGE plpgsql;
+
+\copy (SELECT input, pg_temp.cash_in_out(input) FROM inputs ORDER BY n) TO 'cash_in.tsv'
+
+\copy (SELECT cents, ('0.01'::money * cents)::text FROM unnest(ARRAY[0, 1, -1, 5, 99, 100, -100, 123456, -123456, 100000, 99999999, 123456789012, -9223372036854775807, -9223372036854775808, 9223372036854775807]::int8[]) WITH ORDINALITY AS t(cents, n) ORDER BY n) TO 'cash_out.tsv'